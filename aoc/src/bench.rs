@@ -0,0 +1,97 @@
+// Benchmarks: times the phases of a day's solver, or of one of the
+// alternative solvers kept around for comparison, and optionally appends
+// the timings to a tab-separated report.  Reports from different
+// commits or solvers can then be compared with the usual tools.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::Duration;
+
+use util::bench::{BenchResult, Stats};
+use util::cli::{self, Args, Opt, RunResult, UsageError};
+
+use crate::day;
+
+type BenchFn = fn(&Args, &str, usize) -> BenchResult;
+
+// Alternative solvers, by day number and name.
+const ALTERNATIVES: &[(usize, &str, BenchFn)] = &[
+    (1, "using_find", aoc01::using_find::bench),
+    (8, "brute", aoc08::brute::bench),
+    (17, "using_dijkstra", aoc17::using_dijkstra::bench),
+];
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("runs", "n", "number of times to run each phase (default 10)"),
+    Opt::value("report", "file", "append the timings to the given file"),
+    Opt::value("label", "text", "label for the timings in the report, e.g. a commit"),
+];
+
+const REPORT_HEADER: &str = "label\tday\tsolver\tphase\truns\tmin_ns\tmedian_ns\tmax_ns\n";
+
+struct Solver {
+    day: usize,
+    name: &'static str,
+    options: &'static [Opt],
+    bench: BenchFn,
+}
+
+// The solver named by "<day>" (the main one) or "<day>/<alternative>".
+fn solver(spec: &str) -> Result<Solver, UsageError> {
+    let (d, alternative) = match spec.split_once('/') {
+        Some((d, a)) => (d, Some(a)),
+        None => (spec, None),
+    };
+    let (n, day_solver) = day(d)?;
+    let Some(alternative) = alternative else {
+        return Ok(Solver{ day: n, name: "main", options: day_solver.options, bench: day_solver.bench })
+    };
+    ALTERNATIVES.iter()
+        .find(|&&(m, name, _)| m == n && name == alternative)
+        .map(|&(_, name, bench)| Solver{ day: n, name, options: day_solver.options, bench })
+        .ok_or_else(|| {
+            let known = ALTERNATIVES.iter()
+                .map(|(m, name, _)| format!("{:02}/{}", m, name))
+                .collect::<Vec<_>>();
+            UsageError(format!("no such solver: '{}' (alternatives: {})", spec, known.join(", ")))
+        })
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+// Runs the benchmark for the given solver, with the remaining arguments
+// being the input file, the options above and the day's own options.
+pub fn run(spec: &str, args: &[String]) -> RunResult {
+    let solver = solver(spec)?;
+    let opts = solver.options.iter().chain(OPTIONS).copied().collect::<Vec<_>>();
+    let args = Args::parse(args.iter().cloned(), &opts)?;
+    let runs = args.value_or("runs", 10)?;
+    if runs == 0 {
+        return Err(Box::new(UsageError(String::from("--runs must be positive"))));
+    }
+    let input = cli::read_input(args.file()?)?;
+    let timings = (solver.bench)(&args, &input, runs)?;
+
+    for (phase, Stats{ runs, min, median, max }) in &timings {
+        println!("{:02} {} {}: {} runs, min {:.3} ms, median {:.3} ms, max {:.3} ms",
+                 solver.day, solver.name, phase, runs,
+                 millis(*min), millis(*median), millis(*max));
+    }
+
+    if let Some(report) = args.value::<String>("report")? {
+        let label = args.value_or("label", String::new())?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&report)
+            .map_err(|e| format!("{}: {}", report, e))?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(REPORT_HEADER.as_bytes())?;
+        }
+        for (phase, Stats{ runs, min, median, max }) in &timings {
+            writeln!(file, "{}\t{:02}\t{}\t{}\t{}\t{}\t{}\t{}",
+                     label, solver.day, solver.name, phase, runs,
+                     min.as_nanos(), median.as_nanos(), max.as_nanos())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+// Differential testing: runs a day's main solver and one of its
+// alternative solvers on generated inputs and compares the answers.
+// When they disagree, the input is shrunk for as long as they still do,
+// so that what gets reported is a small counterexample rather than
+// whatever the generator happened to produce.
+//
+// Shrinking only ever tries inputs on which at least one of the solvers
+// gives answers; inputs that neither can parse do not count as
+// disagreements.
+
+use std::fmt;
+
+use util::cli::{Args, Opt, Part, RunResult, UsageError};
+use util::puzzle::{Answer, SolveResult};
+use util::random::Rng;
+
+use crate::day;
+
+type SolveFn = fn(&Args, &str) -> SolveResult;
+type ShrinkFn = fn(&str) -> Vec<String>;
+
+pub struct Pair {
+    pub day: usize,
+    pub name: &'static str,
+    pub alternative: SolveFn,
+    // Generator options used unless given on the command line, keeping
+    // inputs small enough for the slower of the two.
+    pub gen_args: &'static [&'static str],
+    // Smaller variants of an input, most promising first.
+    pub shrink: ShrinkFn,
+}
+
+// Days with alternative solvers, by day number and name.
+pub const PAIRS: &[Pair] = &[
+    Pair{ day: 1, name: "using_find", alternative: aoc01::using_find::solve,
+          gen_args: &["--lines", "20"], shrink: lines_then_letters },
+    Pair{ day: 8, name: "brute", alternative: aoc08::brute::solve,
+          gen_args: &["--ghosts", "3", "--rounds", "13"], shrink: network_lines },
+    Pair{ day: 17, name: "using_dijkstra", alternative: aoc17::using_dijkstra::solve,
+          gen_args: &["--size", "20"], shrink: smaller_map },
+];
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("cases", "n", "number of generated inputs to try (default 100)"),
+    Opt::value("seed", "n", "seed for the first input, counting up from there (default 0)"),
+];
+
+// The pair named by "<day>/<alternative>".
+pub fn pair(spec: &str) -> Result<&'static Pair, UsageError> {
+    let no_such = || {
+        let known = PAIRS.iter()
+            .map(|p| format!("{:02}/{}", p.day, p.name))
+            .collect::<Vec<_>>();
+        UsageError(format!("no such pair: '{}' (pairs: {})", spec, known.join(", ")))
+    };
+    let (d, name) = spec.split_once('/').ok_or_else(no_such)?;
+    let (n, _) = day(d)?;
+    PAIRS.iter().find(|p| p.day == n && p.name == name).ok_or_else(no_such)
+}
+
+type Outcome = Result<Vec<(Part, Answer)>, String>;
+
+fn outcome(solve: SolveFn, input: &str) -> Outcome {
+    let args = Args::parse(std::iter::empty(), &[]).map_err(|e| e.to_string())?;
+    solve(&args, input)
+        .map(|answers| answers.into_iter().map(|s| (s.part, s.answer)).collect())
+        .map_err(|e| e.to_string())
+}
+
+fn show(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(answers) => answers.iter()
+            .map(|(part, answer)| format!("part {}: {}", part, answer))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+// An input on which the two solvers disagree, and what they say.
+pub struct Counterexample {
+    pub seed: u64,
+    pub input: String,
+    pub main: Outcome,
+    pub alternative: Outcome,
+}
+
+impl Pair {
+    fn main(&self) -> SolveFn {
+        crate::DAYS[self.day - 1].solve
+    }
+
+    // What both solvers say, if they disagree.
+    fn disagreement(&self, input: &str) -> Option<(Outcome, Outcome)> {
+        let main = outcome(self.main(), input);
+        let alternative = outcome(self.alternative, input);
+        ((main.is_ok() || alternative.is_ok()) && main != alternative).then_some((main, alternative))
+    }
+
+    // Repeatedly replaces the input by the first smaller variant on
+    // which the solvers still disagree.
+    pub fn shrink(&self, seed: u64, input: String) -> Option<Counterexample> {
+        let (mut main, mut alternative) = self.disagreement(&input)?;
+        let mut input = input;
+        'shrinking: loop {
+            for smaller in (self.shrink)(&input) {
+                if let Some((m, a)) = self.disagreement(&smaller) {
+                    (input, main, alternative) = (smaller, m, a);
+                    continue 'shrinking
+                }
+            }
+            return Some(Counterexample{ seed, input, main, alternative })
+        }
+    }
+
+    // Tries the given number of generated inputs, with consecutive seeds,
+    // and shrinks the first one on which the solvers disagree.
+    pub fn check(&self, args: &Args, seed: u64, cases: u64) -> Result<Option<Counterexample>, UsageError> {
+        let generate = crate::DAYS[self.day - 1].generate;
+        for seed in seed..seed + cases {
+            let input = generate(args, &mut Rng::new(seed))?;
+            if let Some(c) = self.shrink(seed, input) {
+                return Ok(Some(c))
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "from seed {}:", self.seed)?;
+        write!(f, "{}", self.input)?;
+        if !self.input.ends_with('\n') {
+            writeln!(f)?;
+        }
+        writeln!(f, "main: {}", show(&self.main))?;
+        write!(f, "alternative: {}", show(&self.alternative))
+    }
+}
+
+// Runs the differential test for the given pair, with the remaining
+// arguments being the options above and those of the day's generator.
+pub fn run(spec: &str, args: &[String]) -> RunResult {
+    let pair = pair(spec)?;
+    let (_, d) = day(&pair.day.to_string())?;
+    let opts = d.gen_options.iter().chain(OPTIONS).copied().collect::<Vec<_>>();
+    let args = pair.gen_args.iter().map(|s| s.to_string()).chain(args.iter().cloned());
+    let args = Args::parse(args, &opts)?;
+    if !args.files.is_empty() {
+        return Err(Box::new(UsageError(String::from("diff takes no input files"))))
+    }
+    let cases = args.value_or("cases", 100)?;
+    let seed = args.value_or("seed", 0)?;
+    match pair.check(&args, seed, cases)? {
+        None => {
+            println!("{:02} main and {} agree on {} inputs", pair.day, pair.name, cases);
+            Ok(())
+        },
+        Some(c) => {
+            println!("{:02} main and {} disagree, {}", pair.day, pair.name, c);
+            Err(Box::from("solvers disagree"))
+        },
+    }
+}
+
+// Shrinking steps.
+
+// Drops runs of lines, long ones first.
+fn without_lines(input: &str) -> Vec<String> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut smaller = Vec::new();
+    let mut len = lines.len() / 2;
+    while len > 0 {
+        for start in (0..lines.len()).step_by(len) {
+            let kept = lines[..start].iter().chain(lines[(start + len).min(lines.len())..].iter());
+            smaller.push(kept.map(|l| format!("{}\n", l)).collect());
+        }
+        len /= 2;
+    }
+    if lines.len() == 1 {
+        smaller.push(String::new());
+    }
+    smaller
+}
+
+// Drops single characters from lines.
+fn without_letters(input: &str) -> Vec<String> {
+    let mut smaller = Vec::new();
+    for (i, c) in input.char_indices() {
+        if c != '\n' {
+            smaller.push(format!("{}{}", &input[..i], &input[i + c.len_utf8()..]));
+        }
+    }
+    smaller
+}
+
+fn lines_then_letters(input: &str) -> Vec<String> {
+    let mut smaller = without_lines(input);
+    smaller.extend(without_letters(input));
+    smaller
+}
+
+// Nodes that are still referenced make the input unparsable, so only
+// whole unreferenced parts of the network go away.  AAA has to stay for
+// part 1.
+fn network_lines(input: &str) -> Vec<String> {
+    without_lines(input).into_iter()
+        .filter(|s| s.lines().any(|l| l.starts_with("AAA ")))
+        .collect()
+}
+
+// Drops rows and columns, down to the smallest map that ultra crucibles
+// can cross, and lowers heat losses.
+fn smaller_map(input: &str) -> Vec<String> {
+    const MIN: usize = 5;
+    let rows = input.lines().collect::<Vec<_>>();
+    let width = rows.first().map_or(0, |r| r.len());
+    let mut smaller = Vec::new();
+    if rows.len() > MIN {
+        for i in 0..rows.len() {
+            smaller.push(rows.iter().enumerate()
+                .filter(|&(k, _)| k != i)
+                .map(|(_, r)| format!("{}\n", r))
+                .collect());
+        }
+    }
+    if width > MIN && rows.iter().all(|r| r.len() == width) {
+        for j in 0..width {
+            smaller.push(rows.iter()
+                .map(|r| format!("{}{}\n", &r[..j], &r[j + 1..]))
+                .collect());
+        }
+    }
+    for (i, c) in input.char_indices() {
+        if matches!(c, '2'..='9') {
+            smaller.push(format!("{}1{}", &input[..i], &input[i + 1..]));
+        }
+    }
+    smaller
+}
@@ -0,0 +1,68 @@
+// Advent-of-Code 2023
+// All days behind one table, for the dispatcher and the regression checks
+// Author: Matthias Blume
+
+pub mod bench;
+pub mod differential;
+pub mod regression;
+
+use util::bench::BenchResult;
+use util::cli::{Args, Opt, UsageError};
+use util::puzzle::{SolveResult, StreamFn};
+use util::random::Rng;
+
+pub struct Day {
+    pub options: &'static [Opt],
+    pub solve: fn(&Args, &str) -> SolveResult,
+    // For days that can read their input as it streams.
+    pub stream: Option<StreamFn>,
+    pub bench: fn(&Args, &str, usize) -> BenchResult,
+    pub gen_options: &'static [Opt],
+    pub generate: fn(&Args, &mut Rng) -> Result<String, UsageError>,
+}
+
+macro_rules! day {
+    ($d:ident) => {
+        Day{ options: $d::OPTIONS, solve: $d::solve, stream: None, bench: $d::bench,
+             gen_options: $d::generate::OPTIONS, generate: $d::generate::generate }
+    };
+    ($d:ident, stream) => {
+        Day{ stream: Some($d::stream), ..day!($d) }
+    };
+}
+
+// Indexed by day number minus one.
+pub const DAYS: [Day; 25] = [
+    day!(aoc01, stream),
+    day!(aoc02, stream),
+    day!(aoc03, stream),
+    day!(aoc04, stream),
+    day!(aoc05),
+    day!(aoc06),
+    day!(aoc07, stream),
+    day!(aoc08),
+    day!(aoc09, stream),
+    day!(aoc10),
+    day!(aoc11),
+    day!(aoc12, stream),
+    day!(aoc13),
+    day!(aoc14),
+    day!(aoc15, stream),
+    day!(aoc16),
+    day!(aoc17),
+    day!(aoc18),
+    day!(aoc19),
+    day!(aoc20),
+    day!(aoc21),
+    day!(aoc22),
+    day!(aoc23),
+    day!(aoc24),
+    day!(aoc25),
+];
+
+// The day with the given number, and that number.
+pub fn day(s: &str) -> Result<(usize, &'static Day), UsageError> {
+    s.parse::<usize>().ok()
+        .and_then(|n| Some((n, DAYS.get(n.checked_sub(1)?)?)))
+        .ok_or_else(|| UsageError(format!("no such day: '{}'", s)))
+}
@@ -0,0 +1,137 @@
+// Advent-of-Code 2023
+// Single entry point for all days
+// Author: Matthias Blume
+
+use std::env;
+use std::path::Path;
+use std::process;
+
+use aoc::bench;
+use aoc::differential;
+use aoc::regression::Manifest;
+use aoc::{day, Day, DAYS};
+use util::cli::{self, Args, Opt, RunResult, UsageError};
+use util::puzzle;
+use util::random::Rng;
+
+// Default manifest for the regression checks.
+const MANIFEST: &str = "regression.txt";
+
+const SEED: Opt = Opt::value("seed", "n", "seed for the random generator (default 0)");
+
+fn usage(program: &str) -> String {
+    format!("usage: {} run <day> <file>... [--part 1|2] [options]\n\
+             \x20      {} help <day>\n\
+             \x20      {} list\n\
+             \x20      {} check [--record] [manifest]\n\
+             \x20      {} bench <day>[/<alternative>] <file> [--runs n] [--report file] \
+             [--label text] [--part 1|2] [options]\n\
+             \x20      {} gen <day> [--seed n] [options]\n\
+             \x20      {} diff <day>/<alternative> [--cases n] [--seed n] [options]\n",
+            program, program, program, program, program, program, program)
+}
+
+fn list(program: &str) {
+    for (i, d) in DAYS.iter().enumerate() {
+        let names = d.options.iter()
+            .map(|o| format!(" --{}", o.name))
+            .collect::<String>();
+        println!("{} run {:02} <file>...{}", program, i + 1, names);
+    }
+}
+
+// The options of the given day's input generator, with --seed first.
+fn gen_options(day: &Day) -> Vec<Opt> {
+    [SEED].into_iter().chain(day.gen_options.iter().copied()).collect()
+}
+
+// Prints a random input for the given day.
+fn generate(day: &Day, args: &Args) -> RunResult {
+    if !args.files.is_empty() {
+        return Err(Box::new(UsageError(String::from("gen takes no input files"))))
+    }
+    let mut rng = Rng::new(args.value_or("seed", 0)?);
+    print!("{}", (day.generate)(args, &mut rng)?);
+    Ok(())
+}
+
+// Runs the regression checks, or with record, writes the answers
+// obtained back into the manifest.
+fn check(manifest: &str, record: bool) -> RunResult {
+    let mut m = Manifest::read(Path::new(manifest))?;
+    let summary = m.run(record, |c, o| println!("{}: {}", c, o));
+    println!("{}", summary);
+    if record {
+        m.write()?;
+    } else if !summary.ok() {
+        return Err(Box::from("regression checks failed"));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args.first().map(String::as_str).unwrap_or("aoc");
+
+    match &args[1..] {
+        [cmd, d, rest @ ..] if cmd == "run" => match day(d) {
+            Ok((n, day)) => {
+                let sub = format!("{} run {}", program, d);
+                let result = Args::parse(rest.iter().cloned(), day.options)
+                    .map_err(Box::from)
+                    .and_then(|a| match day.stream {
+                        Some(stream) => puzzle::run_stream(n, stream, &a),
+                        None => puzzle::run(n, day.solve, &a),
+                    });
+                cli::report(&sub, day.options, result);
+            },
+            Err(e) => {
+                eprintln!("{}: {}", program, e);
+                process::exit(2);
+            },
+        },
+        [cmd, d] if cmd == "help" => match day(d) {
+            Ok((_, day)) => print!("{}", cli::usage(&format!("{} run {}", program, d), day.options)),
+            Err(e) => {
+                eprintln!("{}: {}", program, e);
+                process::exit(2);
+            },
+        },
+        [cmd, d, rest @ ..] if cmd == "gen" => match day(d) {
+            Ok((_, day)) => {
+                let opts = gen_options(day);
+                let result = Args::parse(rest.iter().cloned(), &opts)
+                    .map_err(Box::from)
+                    .and_then(|a| generate(day, &a));
+                cli::report(&format!("{} gen {}", program, d), &opts, result);
+            },
+            Err(e) => {
+                eprintln!("{}: {}", program, e);
+                process::exit(2);
+            },
+        },
+        [cmd] if cmd == "list" => list(program),
+        [cmd, spec, rest @ ..] if cmd == "bench" =>
+            cli::report(&format!("{} bench {}", program, spec), bench::OPTIONS, bench::run(spec, rest)),
+        [cmd, spec, rest @ ..] if cmd == "diff" =>
+            cli::report(&format!("{} diff {}", program, spec), differential::OPTIONS,
+                        differential::run(spec, rest)),
+        [cmd, rest @ ..] if cmd == "check" => {
+            let record = rest.iter().any(|a| a == "--record");
+            let files = rest.iter().filter(|a| *a != "--record").collect::<Vec<_>>();
+            let manifest = match &files[..] {
+                [] => MANIFEST,
+                [file] => file.as_str(),
+                _ => {
+                    eprint!("{}", usage(program));
+                    process::exit(2);
+                },
+            };
+            cli::report(&format!("{} check", program), &[], check(manifest, record));
+        },
+        _ => {
+            eprint!("{}", usage(program));
+            process::exit(2);
+        },
+    }
+}
@@ -0,0 +1,206 @@
+// Regression checks: runs the days on inputs whose answers are known and
+// compares.
+//
+// The checks are listed in a manifest, one per line:
+//
+//     <day> <file> <part> [options] = <answer>
+//
+// for example "02 input/day02.txt 1 --red 12 --green 13 --blue 14 = 2545".
+// File names are relative to the directory of the manifest.  Blank lines
+// and lines starting with '#' are ignored.  An answer of "?" means that
+// none has been recorded yet.
+//
+// Puzzle inputs are not to be shared, so they are typically not around.
+// Checks whose input file is missing are skipped rather than failed.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use util::cli::{Args, Part};
+use util::parse::{self, Line, ParseError};
+
+use crate::day;
+
+const UNRECORDED: &str = "?";
+
+pub struct Check {
+    pub day: usize,
+    pub file: String,
+    pub part: Part,
+    pub options: Vec<String>,
+    pub expected: Option<String>,
+}
+
+impl Check {
+    fn from(line: &Line) -> Result<Self, ParseError> {
+        let (spec, expected) = line.text.split_once(" = ")
+            .ok_or_else(|| line.fail("expected '<day> <file> <part> [options] = <answer>'"))?;
+        let mut fields = spec.split_whitespace();
+        let mut next = |what: &str| fields.next()
+            .ok_or_else(|| line.fail(&format!("missing {}", what)));
+        let d = next("day")?;
+        let (day_number, _) = day(d).map_err(|_| line.error(d, "no such day"))?;
+        let file = next("file")?.to_string();
+        let part = line.parse(next("part")?, "part")?;
+        let expected = expected.trim();
+        Ok(Check{
+            day: day_number,
+            file,
+            part,
+            options: fields.map(String::from).collect(),
+            expected: (expected != UNRECORDED).then(|| expected.to_string()),
+        })
+    }
+
+    // Runs the day on the input, relative to the given directory.  None
+    // if the input is missing; any other trouble reading it is an error.
+    fn answer(&self, dir: &Path) -> Option<Result<String, Box<dyn Error>>> {
+        let path = dir.join(&self.file);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => return Some(Err(format!("{}: {}", path.display(), e).into())),
+        };
+        Some((|| {
+            let (_, day) = day(&self.day.to_string())?;
+            let args = ["--part".to_string(), self.part.to_string()].into_iter()
+                .chain(self.options.iter().cloned());
+            let args = Args::parse(args, day.options)?;
+            let answers = (day.solve)(&args, &input)?;
+            answers.into_iter()
+                .find(|s| s.part == self.part)
+                .map(|s| s.answer.to_string())
+                .ok_or_else(|| Box::from("no answer"))
+        })())
+    }
+}
+
+// Shows what is checked, without the answer.
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02} {} {}", self.day, self.file, self.part)?;
+        for o in &self.options {
+            write!(f, " {}", o)?;
+        }
+        Ok(())
+    }
+}
+
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    Unrecorded(String),
+    Skipped,
+    Error(String),
+}
+
+use Outcome::*;
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Passed => write!(f, "ok"),
+            Failed(got) => write!(f, "FAILED, got {}", got),
+            Unrecorded(got) => write!(f, "unrecorded, got {}", got),
+            Skipped => write!(f, "skipped, no input"),
+            Error(msg) => write!(f, "ERROR: {}", msg),
+        }
+    }
+}
+
+// Everything else in the manifest is kept as it is.
+enum Entry {
+    Check(Check),
+    Other(String),
+}
+
+pub struct Manifest {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+#[derive(Default, Debug)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unrecorded: usize,
+    pub skipped: usize,
+    pub errors: usize,
+}
+
+impl Summary {
+    pub fn ok(&self) -> bool {
+        self.failed == 0 && self.errors == 0
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} passed, {} failed, {} errors, {} unrecorded, {} skipped",
+               self.passed, self.failed, self.errors, self.unrecorded, self.skipped)
+    }
+}
+
+impl Manifest {
+    pub fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let entries = parse::lines(&text)
+            .map(|line| {
+                let t = line.text.trim();
+                if t.is_empty() || t.starts_with('#') {
+                    Ok(Entry::Other(line.text.to_string()))
+                } else {
+                    Check::from(&line).map(Entry::Check)
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|e| e.in_file(&path.display().to_string()))?;
+        Ok(Manifest{ path: path.to_path_buf(), entries })
+    }
+
+    pub fn write(&self) -> Result<(), Box<dyn Error>> {
+        let text = self.entries.iter()
+            .map(|e| match e {
+                Entry::Check(c) =>
+                    format!("{} = {}\n", c, c.expected.as_deref().unwrap_or(UNRECORDED)),
+                Entry::Other(s) => format!("{}\n", s),
+            })
+            .collect::<String>();
+        fs::write(&self.path, text).map_err(|e| format!("{}: {}", self.path.display(), e).into())
+    }
+
+    // Runs all checks, reporting each outcome as it becomes known.  When
+    // recording, the answers obtained replace the expected ones.
+    pub fn run(&mut self, record: bool, mut report: impl FnMut(&Check, &Outcome)) -> Summary {
+        let dir = self.path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let mut summary = Summary::default();
+        for entry in self.entries.iter_mut() {
+            let Entry::Check(check) = entry else { continue };
+            let outcome = match check.answer(&dir) {
+                None => Skipped,
+                Some(Err(e)) => Error(e.to_string()),
+                Some(Ok(got)) => match &check.expected {
+                    Some(expected) if *expected == got => Passed,
+                    Some(_) => Failed(got),
+                    None => Unrecorded(got),
+                },
+            };
+            report(check, &outcome);
+            match &outcome {
+                Passed => summary.passed += 1,
+                Failed(_) => summary.failed += 1,
+                Unrecorded(_) => summary.unrecorded += 1,
+                Skipped => summary.skipped += 1,
+                Error(_) => summary.errors += 1,
+            }
+            if let (true, Failed(got) | Unrecorded(got)) = (record, outcome) {
+                check.expected = Some(got);
+            }
+        }
+        summary
+    }
+}
@@ -0,0 +1,68 @@
+// Runs the main solvers against their alternatives on generated inputs,
+// and checks that shrinking gets a disagreement down to a small input.
+
+use util::cli::Args;
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+
+use aoc::differential::{Pair, PAIRS};
+
+// The generator options a pair runs with.
+fn gen_args(pair: &Pair) -> Args {
+    let args = pair.gen_args.iter().map(|s| s.to_string());
+    Args::parse(args, aoc::DAYS[pair.day - 1].gen_options).unwrap()
+}
+
+#[test]
+fn pairs_agree() {
+    for pair in PAIRS {
+        if let Some(c) = pair.check(&gen_args(pair), 0, 10).unwrap() {
+            panic!("{:02} main and {} disagree, {}", pair.day, pair.name, c);
+        }
+    }
+}
+
+// Replaces spelled-out digits in order, which gets words sharing a
+// letter, like "twone", wrong.
+struct Replacing;
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+fn calibration(line: &str) -> u32 {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next().unwrap_or(0);
+    10 * first + digits.next_back().unwrap_or(first)
+}
+
+impl Puzzle for Replacing {
+    type Model = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> u32 {
+        lines.iter().map(|l| calibration(l)).sum()
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> u32 {
+        lines.iter()
+            .map(|l| WORDS.iter().zip(1..).fold(l.clone(), |l, (w, d): (_, u32)| l.replace(w, &d.to_string())))
+            .map(|l| calibration(&l))
+            .sum()
+    }
+}
+
+fn replacing(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Replacing, args, input)
+}
+
+#[test]
+fn shrinks_to_overlapping_words() {
+    let pair = Pair{ alternative: replacing, ..PAIRS[0] };
+    let c = pair.check(&gen_args(&pair), 0, 100).unwrap().expect("disagreement");
+    assert_eq!(c.input.lines().count(), 1, "{}", c);
+    assert!(c.input.len() <= 10, "{}", c);
+}
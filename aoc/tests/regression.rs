@@ -0,0 +1,36 @@
+// Runs the checks in the regression manifest at the top of the workspace.
+// Without puzzle inputs, all of them are skipped.
+
+use std::fs;
+use std::path::Path;
+
+use aoc::regression::{Manifest, Outcome};
+
+#[test]
+fn recorded_answers() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../regression.txt");
+    let mut manifest = Manifest::read(&path).unwrap();
+    let mut failures = Vec::new();
+    let summary = manifest.run(false, |check, outcome| {
+        println!("{}: {}", check, outcome);
+        if matches!(outcome, Outcome::Failed(_) | Outcome::Error(_)) {
+            failures.push(format!("{}: {}", check, outcome));
+        }
+    });
+    assert!(summary.ok(), "{}\n{}", summary, failures.join("\n"));
+}
+
+// Only a missing input is skipped; one that cannot be read is an error.
+#[test]
+fn unreadable_inputs() {
+    let dir = std::env::temp_dir().join(format!("aoc-regression-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("latin1.txt"), b"1abc\xe92\n").unwrap();
+    fs::write(dir.join("manifest.txt"), "01 missing.txt 1 = 12\n01 latin1.txt 1 = 12\n").unwrap();
+    let mut manifest = Manifest::read(&dir.join("manifest.txt")).unwrap();
+    let mut outcomes = Vec::new();
+    let summary = manifest.run(false, |_, outcome| outcomes.push(outcome.to_string()));
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!((summary.skipped, summary.errors), (1, 1), "{:?}", outcomes);
+    assert!(!summary.ok());
+}
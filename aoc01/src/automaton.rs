@@ -0,0 +1,90 @@
+// Aho–Corasick automaton recognizing the words of a vocabulary, each
+// standing for a digit, as they end.  Overlapping words are all found:
+// "twone" gives 2 and then 1, and so are words within words.
+//
+// The automaton is built from the word table as a trie whose states are
+// the prefixes of the words.  The failure link of a state is the state
+// for its longest proper suffix that is also a prefix; following the
+// failure links ahead of time turns the trie into a deterministic
+// automaton with a transition for every byte.  Words are matched as
+// bytes, which works for any UTF-8 text because no character's encoding
+// can start in the middle of another's.
+
+use std::collections::VecDeque;
+use std::iter;
+
+type State = usize;
+
+const START: State = 0;
+
+pub struct Automaton {
+    next: Vec<[State; 256]>,
+    // The length and the value of the word that each state stands for,
+    // if it is one.
+    word: Vec<Option<(usize, u32)>>,
+    // For each state, the state for its longest proper suffix that is a
+    // word, or START if there is none.
+    out: Vec<State>,
+}
+
+impl Automaton {
+    pub fn from(words: &[(&str, u32)]) -> Self {
+        let mut trie = Automaton{ next: vec![[START; 256]], word: vec![None], out: Vec::new() };
+        let mut children = vec![Vec::new()];
+        for &(word, value) in words {
+            let mut s = START;
+            for &b in word.as_bytes() {
+                if children[s].iter().all(|&(c, _)| c != b) {
+                    let t = trie.next.len();
+                    trie.next.push([START; 256]);
+                    trie.word.push(None);
+                    children.push(Vec::new());
+                    children[s].push((b, t));
+                }
+                s = children[s].iter().find(|&&(c, _)| c == b).unwrap().1;
+            }
+            if !word.is_empty() {
+                trie.word[s] = Some((word.len(), value));
+            }
+        }
+        // Breadth first, so that the failure state, which is shorter, is
+        // complete before it is needed.
+        let mut fail = vec![START; trie.next.len()];
+        trie.out = vec![START; trie.next.len()];
+        let mut queue = VecDeque::from([START]);
+        while let Some(s) = queue.pop_front() {
+            if s != START {
+                let f = fail[s];
+                trie.next[s] = trie.next[f];
+                trie.out[s] = if trie.word[f].is_some() { f } else { trie.out[f] };
+            }
+            for &(b, t) in &children[s] {
+                fail[t] = if s == START { START } else { trie.next[fail[s]][b as usize] };
+                trie.next[s][b as usize] = t;
+                queue.push_back(t);
+            }
+        }
+        trie
+    }
+
+    // The length and the value of the words that end in the state,
+    // longest first.
+    fn words(&self, s: State) -> impl Iterator<Item = (usize, u32)> + '_ {
+        let first = if self.word[s].is_some() { s } else { self.out[s] };
+        iter::successors(Some(first), |&t| Some(self.out[t]))
+            .take_while(|&t| t != START)
+            .map(|t| self.word[t].unwrap())
+    }
+
+    // The values of all words found in the text, with the byte offset
+    // where each starts, in the order in which they end (and longest
+    // first among those that end together).
+    pub fn values<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, u32)> + 'a {
+        text.bytes().enumerate()
+            .scan(START, |s, (i, b)| {
+                *s = self.next[*s][b as usize];
+                Some((i + 1, *s))
+            })
+            .flat_map(|(end, s)| self.words(s).map(move |(len, v)| (end - len, v)))
+    }
+}
@@ -0,0 +1,70 @@
+// Random inputs: lines of letters with digits and spelled-out digits
+// mixed in, including overlapping ones like "twone".
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("lines", "n", "number of lines (default 1000)"),
+    Opt::value("length", "n", "maximum length of a line (default 50)"),
+    Opt::value("words", "p", "chance of a spelled-out digit at each point (default 0.1)"),
+];
+
+const WORDS: &[&str] = &["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const DIGITS: &[u8] = b"123456789";
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator {
+    pub lines: usize,
+    pub length: usize,
+    pub words: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ lines: 1000, length: 50, words: 0.1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            lines: args.value_in("lines", d.lines, 1..)?,
+            length: args.value_in("length", d.length, 1..)?,
+            words: args.value_in("words", d.words, 0.0..=1.0)?,
+        })
+    }
+
+    // Every line has at least one actual digit, as otherwise part 1
+    // would have no answer for it.
+    fn line(&self, rng: &mut Rng) -> String {
+        let len = 1 + rng.index(self.length);
+        let mut line = String::new();
+        while line.len() < len {
+            if rng.chance(self.words) {
+                let word = rng.choose(WORDS);
+                // Words sharing a letter with what precedes them overlap.
+                let shared = line.ends_with(&word[..1]) && rng.chance(0.5);
+                line.push_str(&word[shared as usize..]);
+            } else if rng.chance(0.2) {
+                line.push(rng.choose(DIGITS) as char);
+            } else {
+                line.push(rng.choose(LETTERS) as char);
+            }
+        }
+        if !line.bytes().any(|b| b.is_ascii_digit()) {
+            let i = rng.index(line.len() + 1);
+            line.insert(i, rng.choose(DIGITS) as char);
+        }
+        line
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.lines).map(|_| self.line(rng) + "\n").collect()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
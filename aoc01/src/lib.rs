@@ -0,0 +1,400 @@
+// Advent-of-Code 2023
+// Day 01
+// Author: Matthias Blume
+
+use std::io::BufRead;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Format, Opt, Part, RunResult, UsageError};
+use util::math::{self, Overflow, Total};
+use util::parse::{Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+
+pub mod automaton;
+pub mod generate;
+pub mod using_find;
+
+use automaton::Automaton;
+
+pub const DAY: usize = 1;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("words", "list", "spelled-out digits for part 2, as word=digit pairs \
+                                 separated by commas (default one=1,...,nine=9)"),
+    Opt::flag("lines", "show the first and last digit and the value of each line before the answers"),
+];
+
+// Digits as written, for both parts.
+pub const DIGITS: &[(&str, u32)] = &[
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9)];
+
+// Digits spelled out, for part 2 unless --words gives others.
+pub const WORDS: &[(&str, u32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9)];
+
+// Reads the --words list: word=digit pairs separated by commas.
+fn read_words(list: &str) -> Result<Vec<(&str, u32)>, UsageError> {
+    list.split(',')
+        .map(|pair| match pair.split_once('=') {
+            Some((word, d)) if !word.is_empty() => match d.parse() {
+                Ok(digit) if digit <= 9 => Ok((word, digit)),
+                _ => Err(UsageError(format!("--words: bad digit in '{}'", pair))),
+            },
+            _ => Err(UsageError(format!("--words: expected word=digit, not '{}'", pair))),
+        })
+        .collect()
+}
+
+// The first and the last digit of a line, each with the byte offset
+// where it starts.  They are the same if the line has only one.
+#[derive(PartialEq, Debug)]
+pub struct Calibration {
+    pub first: (usize, u32),
+    pub last: (usize, u32),
+}
+
+impl Calibration {
+    pub fn value(&self) -> u32 {
+        10 * self.first.1 + self.last.1
+    }
+}
+
+// The first digit is the one that starts first, and the last digit the
+// one that starts last.  Words are recognized as they end, so when a word
+// contains another, the inner one can end first or start last.  Of two
+// words that start at the same place, the longer one counts, which is
+// the one that ends later.
+fn calibration(automaton: &Automaton, line: &str) -> Option<Calibration> {
+    let mut digits = automaton.values(line);
+    let mut c = digits.next().map(|d| Calibration{ first: d, last: d })?;
+    for d in digits {
+        if d.0 <= c.first.0 { c.first = d }
+        if d.0 >= c.last.0 { c.last = d }
+    }
+    Some(c)
+}
+
+// A line without any digit is worth 0.
+fn line_value(automaton: &Automaton, line: &str) -> u32 {
+    calibration(automaton, line).map_or(0, |c| c.value())
+}
+
+pub struct Solver {
+    digits: Automaton,  // part 1 only considers actual digits
+    words: Automaton,   // part 2 also considers digits that are spelled out
+}
+
+impl Solver {
+    pub fn with_words(words: &[(&str, u32)]) -> Self {
+        let all = DIGITS.iter().chain(words).copied().collect::<Vec<_>>();
+        Solver{ digits: Automaton::from(DIGITS), words: Automaton::from(&all) }
+    }
+
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        match args.value::<String>("words")? {
+            Some(list) => Ok(Solver::with_words(&read_words(&list)?)),
+            None => Ok(Solver::default()),
+        }
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::with_words(WORDS)
+    }
+}
+
+// Line values are below 100, but there is no limit on the number of
+// lines.
+fn total(lines: &[String], automaton: &Automaton) -> Result<u64, Overflow> {
+    math::sum(lines.iter().map(|l| Ok(line_value(automaton, l) as u64)))
+}
+
+impl Puzzle for Solver {
+    type Model = Vec<String>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<u64, Overflow> {
+        total(lines, &self.digits)
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<u64, Overflow> {
+        total(lines, &self.words)
+    }
+}
+
+// Each line counts on its own, so only running sums are kept.
+impl Streaming for Solver {
+    type Record = String;
+    type State1 = Total<u64>;
+    type State2 = Total<u64>;
+
+    fn record(&self, line: Line) -> Result<String, ParseError> {
+        Ok(line.text.to_string())
+    }
+
+    fn add1(&self, sum: &mut Total<u64>, line: &String) {
+        sum.add(Ok(line_value(&self.digits, line) as u64))
+    }
+
+    fn add2(&self, sum: &mut Total<u64>, line: &String) {
+        sum.add(Ok(line_value(&self.words, line) as u64))
+    }
+
+    fn answer1(&self, sum: Total<u64>) -> Result<u64, Overflow> {
+        sum.get()
+    }
+
+    fn answer2(&self, sum: Total<u64>) -> Result<u64, Overflow> {
+        sum.get()
+    }
+}
+
+// One row per line and requested part, with the digits found and the
+// value, and for each part a count of the lines without any digit at
+// the end.
+fn report(solver: &Solver, args: &Args, lines: &[String]) -> String {
+    let parts = [(Part::One, &solver.digits), (Part::Two, &solver.words)];
+    let mut report = String::new();
+    let mut missing = [0, 0];
+    for (number, line) in lines.iter().enumerate() {
+        for (i, &(part, automaton)) in parts.iter().enumerate().filter(|(_, (p, _))| args.wants(*p)) {
+            let row = match calibration(automaton, line) {
+                Some(c) => format!("first {} at byte {}, last {} at byte {}, value {}",
+                                   c.first.1, c.first.0, c.last.1, c.last.0, c.value()),
+                None => {
+                    missing[i] += 1;
+                    String::from("no digit, value 0")
+                },
+            };
+            report.push_str(&format!("line {} part {}: {}\n", number + 1, part, row));
+        }
+    }
+    for (&(part, _), n) in parts.iter().zip(missing).filter(|&(_, n)| n > 0) {
+        report.push_str(&format!("warning: part {}: {} line(s) without a digit counted as 0\n", part, n));
+    }
+    report
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let solver = Solver::from(args)?;
+    if args.flag("lines") {
+        if args.format != Format::Text {
+            return Err(UsageError(String::from("--lines needs --format text")).into())
+        }
+        print!("{}", report(&solver, args, &solver.parse(input)?));
+    }
+    puzzle::solve(&solver, args, input)
+}
+
+// The report needs all lines, so it does without streaming.
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    if args.flag("lines") {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        return solve(args, &text)
+    }
+    puzzle::solve_stream(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    const EXAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn example_part1() {
+        let solver = Solver::default();
+        let model = solver.parse(EXAMPLE1).unwrap();
+        assert_eq!(solver.part1(&model), Ok(142));
+    }
+
+    #[test]
+    fn example_part2() {
+        let solver = Solver::default();
+        let model = solver.parse(EXAMPLE2).unwrap();
+        assert_eq!(solver.part2(&model), Ok(281));
+    }
+
+    #[test]
+    fn streaming() {
+        let solver = Solver::default();
+        let answers = puzzle::stream_answers(&solver, &mut EXAMPLE2.as_bytes()).unwrap();
+        let model = solver.parse(EXAMPLE2).unwrap();
+        assert_eq!(answers, (solver.part1(&model), solver.part2(&model)));
+    }
+
+    // The digits recognized along s, spelled out or not.
+    fn digits(s: &str) -> Vec<u32> {
+        Solver::default().words.values(s).map(|(_, d)| d).collect()
+    }
+
+    #[test]
+    fn automaton_overlapping_words() {
+        assert_eq!(digits("twone"), [2, 1]);
+        assert_eq!(digits("oneight"), [1, 8]);
+        assert_eq!(digits("sevenine"), [7, 9]);
+        assert_eq!(digits("nineight"), [9, 8]);
+        assert_eq!(digits("fiveight"), [5, 8]);
+        assert_eq!(digits("eightwothree"), [8, 2, 3]);
+    }
+
+    #[test]
+    fn automaton_restarts_after_partial_words() {
+        assert_eq!(digits("fone"), [1]);
+        assert_eq!(digits("ffive"), [5]);
+        assert_eq!(digits("thrthree"), [3]);
+        assert_eq!(digits("sixteen"), [6]);
+        assert_eq!(digits("4nineeightseven2"), [4, 9, 8, 7, 2]);
+        assert_eq!(digits("zero"), []);
+    }
+
+    #[test]
+    fn automaton_positions() {
+        let automaton = Automaton::from(WORDS);
+        assert_eq!(automaton.values("xtwone3").collect::<Vec<_>>(), [(1, 2), (3, 1)]);
+    }
+
+    #[test]
+    fn custom_words() {
+        let solver = Solver::with_words(&[("zero", 0), ("zwei", 2), ("fünf", 5), ("one", 1)]);
+        assert_eq!(line_value(&solver.words, "zerozweione"), 1);
+        assert_eq!(line_value(&solver.words, "zweifünf"), 25);
+        assert_eq!(line_value(&solver.words, "two7"), 77);
+        assert_eq!(line_value(&solver.digits, "fünf"), 0);
+    }
+
+    // Words within words: the first digit is the one that starts first,
+    // even if another one ends before it.
+    #[test]
+    fn nested_words() {
+        let solver = Solver::with_words(&[("abc", 1), ("b", 2)]);
+        assert_eq!(line_value(&solver.words, "abc"), 12);
+        assert_eq!(line_value(&solver.words, "abc"), using_find::line_value("abc", &[("abc", 1), ("b", 2)]));
+        let solver = Solver::with_words(&[("on", 5), ("one", 1), ("e", 3)]);
+        assert_eq!(line_value(&solver.words, "one"), 13);
+    }
+
+    // Random vocabularies over a small alphabet, so that words often
+    // contain or overlap each other, give the same values as str::find.
+    #[test]
+    fn random_words_agree_with_find() {
+        let mut rng = Rng::new(0);
+        for _ in 0..200 {
+            let words = (0..1 + rng.index(5))
+                .map(|_| {
+                    let len = 1 + rng.index(3);
+                    (rng.word(len, b"ab1"), rng.index(10) as u32)
+                })
+                .collect::<Vec<_>>();
+            let words = words.iter().map(|(w, v)| (w.as_str(), *v)).collect::<Vec<_>>();
+            let solver = Solver::with_words(&words);
+            for _ in 0..20 {
+                let len = rng.index(8);
+                let line = rng.word(len, b"ab1c");
+                assert_eq!(line_value(&solver.words, &line), using_find::line_value(&line, &words),
+                           "{:?} {}", words, line);
+            }
+        }
+    }
+
+    #[test]
+    fn words_option() {
+        let args = Args::parse(["--words=un=1,deux=2".to_string()], OPTIONS).unwrap();
+        let solver = Solver::from(&args).unwrap();
+        assert_eq!(line_value(&solver.words, "undeuxtwo"), 12);
+        for bad in ["--words=un", "--words==1", "--words=dix=10", "--words=un=1,"] {
+            let args = Args::parse([bad.to_string()], OPTIONS).unwrap();
+            assert!(Solver::from(&args).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn calibrations() {
+        let solver = Solver::default();
+        assert_eq!(calibration(&solver.words, "xtwone3four"),
+                   Some(Calibration{ first: (1, 2), last: (7, 4) }));
+        assert_eq!(calibration(&solver.digits, "xtwone3four"),
+                   Some(Calibration{ first: (6, 3), last: (6, 3) }));
+        assert_eq!(calibration(&solver.digits, "zero"), None);
+    }
+
+    #[test]
+    fn report_flags_lines_without_digits() {
+        let solver = Solver::default();
+        let lines = solver.parse("a1b2\nthree\n").unwrap();
+        let args = Args::parse([], OPTIONS).unwrap();
+        assert_eq!(report(&solver, &args, &lines), "\
+line 1 part 1: first 1 at byte 1, last 2 at byte 3, value 12
+line 1 part 2: first 1 at byte 1, last 2 at byte 3, value 12
+line 2 part 1: no digit, value 0
+line 2 part 2: first 3 at byte 0, last 3 at byte 0, value 33
+warning: part 1: 1 line(s) without a digit counted as 0
+");
+        let args = Args::parse(["--part=2".to_string()], OPTIONS).unwrap();
+        assert_eq!(report(&solver, &args, &lines), "\
+line 1 part 2: first 1 at byte 1, last 2 at byte 3, value 12
+line 2 part 2: first 3 at byte 0, last 3 at byte 0, value 33
+");
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let solver = Solver::default();
+        let gen = generate::Generator{ lines: 100, ..Default::default() };
+        for seed in 0..20 {
+            let model = solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            for line in &model {
+                assert!((11..=99).contains(&line_value(&solver.digits, line)), "{}", line);
+                assert!((11..=99).contains(&line_value(&solver.words, line)), "{}", line);
+            }
+        }
+    }
+}
@@ -1,67 +1,76 @@
-use std::env;
-use std::fs;
+// Alternative solution using str::find and str::rfind
+// instead of the automaton.
 
-const DIGITS: &[(&str, u32)] = &[
-    ("0", 0),
-    ("one", 1),
-    ("1", 1),
-    ("two", 2),
-    ("2", 2),
-    ("three", 3),
-    ("3", 3),
-    ("four", 4),
-    ("4", 4),
-    ("five", 5),
-    ("5", 5),
-    ("six", 6),
-    ("6", 6),
-    ("seven", 7),
-    ("7", 7),
-    ("eight", 8),
-    ("8", 8),
-    ("nine", 9),
-    ("9", 9)];
+use util::bench::{self, BenchResult};
+use util::cli::Args;
+use util::math::{self, Overflow};
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
 
-fn first_digit(line: &str) -> u32 {
-    let mut idx: usize = std::usize::MAX;
+use super::{DIGITS, WORDS};
+
+// Of two words found at the same place, the longer one counts.
+fn first_digit<'a>(line: &str, table: impl Iterator<Item = &'a (&'a str, u32)>) -> u32 {
+    let mut idx: usize = usize::MAX;
+    let mut len: usize = 0;
     let mut val: u32 = 0;
-    for (s, v) in DIGITS {
+    for (s, v) in table {
         match line.find(s) {
-            None => (),
-            Some(i) => { if i <= idx { idx = i; val = *v } },
+            Some(i) if i < idx || (i == idx && s.len() >= len) => { idx = i; len = s.len(); val = *v },
+            _ => (),
         }
     }
     val
 }
 
-fn last_digit(line: &str) -> u32 {
+fn last_digit<'a>(line: &str, table: impl Iterator<Item = &'a (&'a str, u32)>) -> u32 {
     let mut idx: usize = 0;
+    let mut len: usize = 0;
     let mut val: u32 = 0;
-    for (s, v) in DIGITS {
+    for (s, v) in table {
         match line.rfind(s) {
-            None => (),
-            Some(i) => { if i >= idx { idx = i; val = *v } },
+            Some(i) if i > idx || (i == idx && s.len() >= len) => { idx = i; len = s.len(); val = *v },
+            _ => (),
         }
     }
     val
 }
 
-fn line_value(line: &str) -> u32 {
-    10 * first_digit(line) + last_digit(line)
+// The value of the line with the given words besides the digits.
+pub fn line_value(line: &str, words: &[(&str, u32)]) -> u32 {
+    let table = || DIGITS.iter().chain(words);
+    10 * first_digit(line, table()) + last_digit(line, table())
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+// The sum of the line values, with the given words besides the digits.
+fn total(lines: &[String], words: &[(&str, u32)]) -> Result<u64, Overflow> {
+    math::sum(lines.iter().map(|l| Ok(line_value(l, words) as u64)))
+}
 
-    let file_path = &args[1];
+pub struct Solver;
 
-    let contents = fs::read_to_string(file_path)
-        .expect("Could not read file");
+impl Puzzle for Solver {
+    type Model = Vec<String>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
 
-    let mut tally: u32 = 0;
+    fn parse(&self, input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<u64, Overflow> {
+        total(lines, &[])
+    }
 
-    for line in contents.lines() {
-        tally += line_value(line)
+    fn part2(&self, lines: &Vec<String>) -> Result<u64, Overflow> {
+        total(lines, WORDS)
     }
-    println!("tally is {tally}");
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
 }
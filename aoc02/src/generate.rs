@@ -0,0 +1,61 @@
+// Random inputs: games of draws of up to three colors.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("games", "n", "number of games (default 100)"),
+    Opt::value("draws", "n", "maximum number of draws per game (default 6)"),
+    Opt::value("max", "n", "maximum number of cubes of a color in a draw (default 20)"),
+];
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+pub struct Generator {
+    pub games: usize,
+    pub draws: usize,
+    pub max: u32,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ games: 100, draws: 6, max: 20 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            games: args.value_in("games", d.games, 1..)?,
+            draws: args.value_in("draws", d.draws, 1..)?,
+            max: args.value_in("max", d.max, 1..)?,
+        })
+    }
+
+    // Each color appears at most once, in any order.
+    fn draw(&self, rng: &mut Rng) -> String {
+        let mut colors = COLORS;
+        rng.shuffle(&mut colors);
+        let n = 1 + rng.index(colors.len());
+        colors[..n].iter()
+            .map(|c| format!("{} {}", rng.range(1..=self.max as i64), c))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        (1..=self.games)
+            .map(|i| {
+                let draws = (0..1 + rng.index(self.draws))
+                    .map(|_| self.draw(rng))
+                    .collect::<Vec<_>>();
+                format!("Game {}: {}\n", i, draws.join("; "))
+            })
+            .collect()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
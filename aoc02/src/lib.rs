@@ -0,0 +1,348 @@
+// Advent-of-Code 2023
+// Day 02
+// Author: Matthias Blume
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Format, Opt, Part, RunResult, UsageError};
+use util::math::{self, Count, Overflow, Total};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 2;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("red", "n", "number of red cubes in the bag (default 12)"),
+    Opt::value("green", "n", "number of green cubes in the bag (default 13)"),
+    Opt::value("blue", "n", "number of blue cubes in the bag (default 14)"),
+    Opt::value("bag", "list", "cubes of any colors in the bag, as color=n pairs separated by commas"),
+    Opt::flag("games", "show whether the bag admits each game and the smallest bag that would"),
+];
+
+// The bag of the puzzle, which --red, --green and --blue change.
+const BAG: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+// Numbers of cubes by color, as shown in one draw or held by a bag.
+// Colors that are not mentioned count as none.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct Draw(BTreeMap<String, u32>);
+
+impl Draw {
+    pub fn from<'a>(cubes: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Draw(cubes.into_iter().map(|(color, n)| (color.to_string(), n)).collect())
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn fits_in(&self, bag: &Draw) -> bool {
+        self.0.iter().all(|(color, &n)| n <= bag.count(color))
+    }
+
+    // Grows the bag so that the draw fits in it.
+    fn cover(&mut self, draw: &Draw) {
+        for (color, &n) in &draw.0 {
+            let m = self.0.entry(color.clone()).or_default();
+            *m = (*m).max(n);
+        }
+    }
+
+    // The product of the numbers of cubes of all colors that the bag or
+    // the draw mention.  (The puzzle's bag makes it red * green * blue.)
+    pub fn power(&self, bag: &Draw) -> Result<u64, Overflow> {
+        let others = self.0.keys().filter(|color| !bag.0.contains_key(*color));
+        bag.0.keys().chain(others)
+            .try_fold(1u64, |p, color| p.times(&(self.count(color) as u64)))
+    }
+}
+
+impl fmt::Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes = self.0.iter().map(|(color, n)| format!("{} {}", n, color)).collect::<Vec<_>>();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+pub struct Game {
+    pub number: u32,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    pub fn possible(&self, bag: &Draw) -> bool {
+        self.draws.iter().all(|d| d.fits_in(bag))
+    }
+
+    // Notice the duality between min and max here.  The minimum *necessary*
+    // bag is computed by taking the color-wise max over all draws.
+    pub fn minimal_bag(&self) -> Draw {
+        let mut bag = Draw::default();
+        self.draws.iter().for_each(|d| bag.cover(d));
+        bag
+    }
+}
+
+fn read_cubes<'a>(line: &Line, input: &'a str) -> Result<(&'a str, u32), ParseError> {
+    match input.split_whitespace().boxed()[..] {
+        [value_str, color] => Ok((color, line.parse(value_str, "color value")?)),
+        _ => Err(line.error(input, "expected count and color")),
+    }
+}
+
+fn read_draw(line: &Line, input: &str) -> Result<Draw, ParseError> {
+    let mut draw = Draw::default();
+    for s in input.split(',') {
+        let (color, n) = read_cubes(line, s)?;
+        let m = draw.0.entry(color.to_string()).or_default();
+        *m = m.checked_add(n).ok_or_else(|| line.error(input, "too many cubes"))?;
+    }
+    Ok(draw)
+}
+
+fn read_game_number(line: &Line, input: &str) -> Result<u32, ParseError> {
+    match input.split_whitespace().boxed()[..] {
+        ["Game", number_str] => line.parse(number_str, "game number"),
+        _ => Err(line.error(input, "missing or malformed Game spec")),
+    }
+}
+
+fn read_game(line: Line) -> Result<Game, ParseError> {
+    match line.text.split(':').boxed()[..] {
+        [game, draws] => Ok(Game{
+            number: read_game_number(&line, game)?,
+            draws: draws.split(';').map(|s| read_draw(&line, s)).collect::<Result<_, _>>()?,
+        }),
+        _ => Err(line.fail("expected 'Game <n>: <draws>'")),
+    }
+}
+
+// Reads the --bag list: color=n pairs separated by commas.
+fn read_bag(list: &str, bag: &mut Draw) -> Result<(), UsageError> {
+    for pair in list.split(',') {
+        match pair.split_once('=') {
+            Some((color, n)) if !color.is_empty() => {
+                let n = n.parse().map_err(|_| UsageError(format!("--bag: bad number in '{}'", pair)))?;
+                bag.0.insert(color.to_string(), n);
+            },
+            _ => return Err(UsageError(format!("--bag: expected color=n, not '{}'", pair))),
+        }
+    }
+    Ok(())
+}
+
+pub struct Solver {
+    bag: Draw,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let mut bag = Draw::default();
+        for (color, n) in BAG {
+            bag.0.insert(color.to_string(), args.value_or(color, n)?);
+        }
+        if let Some(list) = args.value::<String>("bag")? {
+            read_bag(&list, &mut bag)?;
+        }
+        Ok(Solver{ bag })
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver{ bag: Draw::from(BAG) }
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Vec<Game>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse::lines(input).map(read_game).collect()
+    }
+
+    fn part1(&self, games: &Self::Model) -> Result<u64, Overflow> {
+        math::sum(games.iter()
+            .filter(|g| g.possible(&self.bag))
+            .map(|g| Ok(g.number as u64)))
+    }
+
+    fn part2(&self, games: &Self::Model) -> Result<u64, Overflow> {
+        math::sum(games.iter().map(|g| g.minimal_bag().power(&self.bag)))
+    }
+}
+
+// Each game counts on its own, so only running sums are kept.
+impl Streaming for Solver {
+    type Record = Game;
+    type State1 = Total<u64>;
+    type State2 = Total<u64>;
+
+    fn record(&self, line: Line) -> Result<Game, ParseError> {
+        read_game(line)
+    }
+
+    fn add1(&self, sum: &mut Total<u64>, game: &Game) {
+        if game.possible(&self.bag) {
+            sum.add(Ok(game.number as u64))
+        }
+    }
+
+    fn add2(&self, sum: &mut Total<u64>, game: &Game) {
+        sum.add(game.minimal_bag().power(&self.bag))
+    }
+
+    fn answer1(&self, sum: Total<u64>) -> Result<u64, Overflow> {
+        sum.get()
+    }
+
+    fn answer2(&self, sum: Total<u64>) -> Result<u64, Overflow> {
+        sum.get()
+    }
+}
+
+// One row per game, telling whether the bag admits it (part 1) and what
+// the smallest bag that does is (part 2).
+fn report(solver: &Solver, args: &Args, games: &[Game]) -> String {
+    let mut report = String::new();
+    for game in games {
+        let mut row = Vec::new();
+        if args.wants(Part::One) {
+            row.push(String::from(if game.possible(&solver.bag) { "possible" } else { "impossible" }));
+        }
+        if args.wants(Part::Two) {
+            let bag = game.minimal_bag();
+            let power = bag.power(&solver.bag).map_or_else(|e| e.to_string(), |p| p.to_string());
+            row.push(format!("minimal bag {}, power {}", bag, power));
+        }
+        report.push_str(&format!("Game {}: {}\n", game.number, row.join("; ")));
+    }
+    report
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let solver = Solver::from(args)?;
+    if args.flag("games") {
+        if args.format != Format::Text {
+            return Err(UsageError(String::from("--games needs --format text")).into())
+        }
+        print!("{}", report(&solver, args, &solver.parse(input)?));
+    }
+    puzzle::solve(&solver, args, input)
+}
+
+// The report comes before the answers, so it does without streaming.
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    if args.flag("games") {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        return solve(args, &text)
+    }
+    puzzle::solve_stream(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver::default();
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), Ok(8));
+        assert_eq!(solver.part2(&model), Ok(2286));
+    }
+
+    #[test]
+    fn streaming() {
+        let solver = Solver::default();
+        let answers = puzzle::stream_answers(&solver, &mut EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(answers, (Ok(8), Ok(2286)));
+    }
+
+    #[test]
+    fn games() {
+        let solver = Solver::default();
+        let games = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(games[0].minimal_bag(), Draw::from([("red", 4), ("green", 2), ("blue", 6)]));
+        assert!(games[0].possible(&solver.bag));
+        assert!(!games[2].possible(&solver.bag));
+        let args = Args::parse(["--part=1".to_string()], OPTIONS).unwrap();
+        assert_eq!(report(&solver, &args, &games[..3]), "\
+Game 1: possible
+Game 2: possible
+Game 3: impossible
+");
+        let args = Args::parse([], OPTIONS).unwrap();
+        assert_eq!(report(&solver, &args, &games[..1]),
+                   "Game 1: possible; minimal bag 6 blue, 2 green, 4 red, power 48\n");
+    }
+
+    // Any color goes, and a color that the bag lacks rules a game out.
+    // Colors of the bag that a game never shows make its power 0.
+    #[test]
+    fn other_colors() {
+        let args = Args::parse(["--bag=yellow=2,red=5".to_string(), "--blue=1".to_string()], OPTIONS).unwrap();
+        let solver = Solver::from(&args).unwrap();
+        assert_eq!(solver.bag, Draw::from([("red", 5), ("green", 13), ("blue", 1), ("yellow", 2)]));
+        let games = solver.parse("\
+Game 1: 2 yellow, 5 red; 1 blue, 3 green
+Game 2: 1 purple, 1 red, 1 green, 1 blue, 1 yellow
+Game 3: 3 yellow, 1 red, 1 green, 1 blue
+Game 4: 1 red, 1 green, 1 blue
+").unwrap();
+        assert_eq!(solver.part1(&games), Ok(1 + 4));
+        assert_eq!(solver.part2(&games), Ok(30 + 1 + 3));
+        for bad in ["--bag=yellow", "--bag==2", "--bag=yellow=-1", "--red=x"] {
+            let args = Args::parse([bad.to_string()], OPTIONS).unwrap();
+            assert!(Solver::from(&args).is_err(), "{}", bad);
+        }
+    }
+
+    // The power of a game can exceed 64 bits.
+    #[test]
+    fn overflow() {
+        let solver = Solver::default();
+        let big = "Game 1: 4000000000 red, 4000000000 green, 4000000000 blue\n";
+        assert_eq!(solver.part2(&solver.parse(big).unwrap()), Err(Overflow));
+        let e = solver.parse("Game 1: 4000000000 red, 4000000000 red\n").err().unwrap();
+        assert_eq!(e.msg, "too many cubes");
+    }
+
+    // A bag with the most cubes ever drawn admits every game.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ games: 50, ..Default::default() };
+        let solver = Solver{ bag: Draw::from([("red", gen.max), ("green", gen.max), ("blue", gen.max)]) };
+        for seed in 0..20 {
+            let model = solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            assert_eq!(solver.part1(&model), Ok(50 * 51 / 2));
+            assert!(solver.part2(&model).unwrap() <= 50 * (gen.max as u64).pow(3));
+        }
+    }
+}
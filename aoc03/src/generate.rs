@@ -0,0 +1,64 @@
+// Random inputs: an engine schematic with numbers and symbols scattered
+// over it.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the schematic (default 140)"),
+    Opt::value("numbers", "p", "chance of a number starting at each free cell (default 0.1)"),
+    Opt::value("symbols", "p", "chance of a symbol at each remaining cell (default 0.1)"),
+];
+
+const SYMBOLS: &[char] = &['*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+
+pub struct Generator {
+    pub size: usize,
+    pub numbers: f64,
+    pub symbols: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 140, numbers: 0.1, symbols: 0.1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 1..)?,
+            numbers: args.value_in("numbers", d.numbers, 0.0..=1.0)?,
+            symbols: args.value_in("symbols", d.symbols, 0.0..=1.0)?,
+        })
+    }
+
+    // Numbers have up to three digits and are never right next to
+    // each other, which would make them one number.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut grid = Grid::filled(self.size, self.size, '.');
+        for r in 0..self.size {
+            let mut c = 0;
+            while c < self.size {
+                if rng.chance(self.numbers) {
+                    let len = (1 + rng.index(3)).min(self.size - c);
+                    let number = rng.range(10i64.pow(len as u32 - 1)..=10i64.pow(len as u32) - 1);
+                    for (i, d) in number.to_string().chars().enumerate() {
+                        grid[(r, c + i)] = d;
+                    }
+                    c += len;
+                } else if rng.chance(self.symbols) {
+                    grid[(r, c)] = rng.choose(SYMBOLS);
+                }
+                c += 1;
+            }
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,455 @@
+// Advent-of-Code 2023
+// Day 03
+// Author: Matthias Blume
+
+use std::fs;
+use std::io::BufRead;
+use std::ops::Range;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Format, Opt, RunResult, UsageError};
+use util::math::{self, Count, Overflow, Total};
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+
+pub mod generate;
+
+pub const DAY: usize = 3;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("gears", "symbols", "symbols that can be gears in part 2 (default *)"),
+    Opt::value("arity", "n", "number of parts a gear touches in part 2 (default 2)"),
+    Opt::value("touching", "symbols", "list the parts next to any of the given symbols"),
+    Opt::flag("loners", "list the numbers next to no symbol"),
+    Opt::value("graph", "file", "write the graph of numbers and the symbols they touch to the given .dot file"),
+];
+
+// A number of the schematic, with its row and its first and last
+// column (inclusive), all 0-based.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Number {
+    pub value: u64,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// Anything other than a digit or '.' is a symbol.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Symbol {
+    pub c: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Number {
+    // Next to the symbol horizontally, vertically, or diagonally?
+    fn touches(&self, s: &Symbol) -> bool {
+        self.row.abs_diff(s.row) <= 1 && s.col + 1 >= self.start && s.col <= self.end + 1
+    }
+}
+
+// The numbers and symbols of one row.
+#[derive(Clone, Default)]
+pub struct Row {
+    numbers: Vec<(u64, usize, usize)>,
+    symbols: Vec<(char, usize)>,
+}
+
+fn read_row(line: parse::Line) -> Result<Row, ParseError> {
+    let mut row = Row::default();
+    let mut cur: Option<(u64, usize)> = None;
+    for (col, c) in line.text.chars().enumerate() {
+        match (c.to_digit(10), cur) {
+            (Some(digit), Some((value, start))) => {
+                let value = value.checked_mul(10).and_then(|v| v.checked_add(digit as u64))
+                    .ok_or_else(|| line.error_at(start, line.text.chars().nth(start).unwrap(), "number too large"))?;
+                cur = Some((value, start))
+            },
+            (Some(digit), None) => cur = Some((digit as u64, col)),
+            (None, Some((value, start))) => { row.numbers.push((value, start, col - 1)); cur = None },
+            _ => (),
+        }
+        if c != '.' && !c.is_ascii_digit() {
+            row.symbols.push((c, col));
+        }
+    }
+    if let Some((value, start)) = cur {
+        row.numbers.push((value, start, line.text.chars().count() - 1))
+    }
+    Ok(row)
+}
+
+// The schematic as a graph: numbers and symbols, with an edge between
+// each number and each symbol next to it.  Rows are added one at a time
+// and only need to be linked to the row before them.
+#[derive(Default)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    // The edges, by number and by symbol, as indices into the other list.
+    symbols_of: Vec<Vec<usize>>,
+    numbers_of: Vec<Vec<usize>>,
+    rows: usize,
+    // Where the numbers and symbols of the last row start.
+    last_row: (usize, usize),
+}
+
+impl Schematic {
+    pub fn push(&mut self, row: &Row) {
+        let (n0, s0) = (self.numbers.len(), self.symbols.len());
+        let r = self.rows;
+        self.numbers.extend(row.numbers.iter().map(|&(value, start, end)| Number{ value, row: r, start, end }));
+        self.symbols.extend(row.symbols.iter().map(|&(c, col)| Symbol{ c, row: r, col }));
+        self.symbols_of.resize(self.numbers.len(), Vec::new());
+        self.numbers_of.resize(self.symbols.len(), Vec::new());
+        // New numbers with the symbols of both rows, then the numbers of
+        // the row before with the new symbols.
+        let pairs = (n0..self.numbers.len()).flat_map(|n| (self.last_row.1..self.symbols.len()).map(move |s| (n, s)))
+            .chain((self.last_row.0..n0).flat_map(|n| (s0..self.symbols.len()).map(move |s| (n, s))))
+            .collect::<Vec<_>>();
+        for (n, s) in pairs {
+            if self.numbers[n].touches(&self.symbols[s]) {
+                self.symbols_of[n].push(s);
+                self.numbers_of[s].push(n);
+            }
+        }
+        self.last_row = (n0, s0);
+        self.rows += 1;
+    }
+
+    // The numbers next to the given symbol.
+    pub fn neighbors(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.numbers_of[symbol].iter().map(|&n| &self.numbers[n])
+    }
+
+    // The part numbers: those next to some symbol.
+    pub fn parts(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().zip(&self.symbols_of).filter(|(_, s)| !s.is_empty()).map(|(n, _)| n)
+    }
+
+    // The numbers next to no symbol.
+    pub fn loners(&self) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().zip(&self.symbols_of).filter(|(_, s)| s.is_empty()).map(|(n, _)| n)
+    }
+
+    // The parts next to any of the given symbols, each once.
+    pub fn touching<'a>(&'a self, symbols: &'a str) -> impl Iterator<Item = &'a Number> {
+        self.numbers.iter().zip(&self.symbols_of)
+            .filter(|(_, s)| s.iter().any(|&s| symbols.contains(self.symbols[s].c)))
+            .map(|(n, _)| n)
+    }
+
+    // The gears, each with the parts it touches.
+    pub fn gears<'a>(&'a self, gear: &'a Gear) -> impl Iterator<Item = (&'a Symbol, Vec<&'a Number>)> {
+        self.symbols.iter().enumerate()
+            .filter(|&(s, symbol)| gear.symbols.contains(symbol.c) && self.numbers_of[s].len() == gear.arity)
+            .map(|(s, symbol)| (symbol, self.neighbors(s).collect()))
+    }
+
+    // The graph in Graphviz's format, with positions as line:column.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph schematic {\n");
+        for (i, n) in self.numbers.iter().enumerate() {
+            dot.push_str(&format!("  n{} [label=\"{} @ {}:{}\"];\n", i, n.value, n.row + 1, n.start + 1));
+        }
+        for (i, s) in self.symbols.iter().enumerate() {
+            let c = if s.c == '"' || s.c == '\\' { format!("\\{}", s.c) } else { s.c.to_string() };
+            dot.push_str(&format!("  s{} [label=\"{} @ {}:{}\", shape=box];\n", i, c, s.row + 1, s.col + 1));
+        }
+        for (n, symbols) in self.symbols_of.iter().enumerate() {
+            for s in symbols {
+                dot.push_str(&format!("  n{} -- s{};\n", n, s));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// What makes a symbol a gear: being one of the given symbols and
+// touching exactly the given number of parts.
+pub struct Gear {
+    pub symbols: String,
+    pub arity: usize,
+}
+
+impl Default for Gear {
+    fn default() -> Self {
+        Gear{ symbols: String::from("*"), arity: 2 }
+    }
+}
+
+type Sum = Result<u64, Overflow>;
+
+// The sums only count the parts and gears in the given rows.
+fn parts_sum(schematic: &Schematic, rows: Range<usize>) -> Sum {
+    math::sum(schematic.parts().filter(|n| rows.contains(&n.row)).map(|n| Ok(n.value)))
+}
+
+fn gear_ratio_sum(schematic: &Schematic, gear: &Gear, rows: Range<usize>) -> Sum {
+    math::sum(schematic.gears(gear)
+        .filter(|(s, _)| rows.contains(&s.row))
+        .map(|(_, parts)| parts.iter().try_fold(1u64, |p, n| p.times(&n.value))))
+}
+
+// The same sums for rows that arrive one at a time: only the last two
+// rows are kept, and the sum covers the rows before them.  Each row is
+// counted in a schematic of just itself and its two neighbors, using an
+// empty row as the neighbor of the first and the last row.
+#[derive(Default)]
+pub struct Window {
+    prev: Option<Row>,
+    cur: Option<Row>,
+    sum: Total<u64>,
+}
+
+fn neighborhood(prev: Option<&Row>, cur: &Row, next: Option<&Row>) -> Schematic {
+    let empty = Row::default();
+    let mut schematic = Schematic::default();
+    for row in [prev.unwrap_or(&empty), cur, next.unwrap_or(&empty)] {
+        schematic.push(row);
+    }
+    schematic
+}
+
+impl Window {
+    fn push(&mut self, next: &Row, f: impl Fn(&Schematic, Range<usize>) -> Sum) {
+        if let Some(cur) = self.cur.take() {
+            self.sum.add(f(&neighborhood(self.prev.as_ref(), &cur, Some(next)), 1..2));
+            self.prev = Some(cur);
+        }
+        self.cur = Some(next.clone());
+    }
+
+    fn finish(mut self, f: impl Fn(&Schematic, Range<usize>) -> Sum) -> Sum {
+        if let Some(cur) = &self.cur {
+            self.sum.add(f(&neighborhood(self.prev.as_ref(), cur, None), 1..2));
+        }
+        self.sum.get()
+    }
+}
+
+#[derive(Default)]
+pub struct Solver {
+    gear: Gear,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Gear::default();
+        Ok(Solver{ gear: Gear{
+            symbols: args.value_or("gears", d.symbols)?,
+            arity: args.value_in("arity", d.arity, 1..)?,
+        }})
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Schematic;
+    type Answer1 = Sum;
+    type Answer2 = Sum;
+
+    // Every character is meaningful (anything other than digits and
+    // '.' counts as a symbol), so parsing only fails for numbers that
+    // are too large.
+    fn parse(&self, input: &str) -> Result<Schematic, ParseError> {
+        let mut schematic = Schematic::default();
+        for line in parse::lines(input) {
+            schematic.push(&read_row(line)?);
+        }
+        Ok(schematic)
+    }
+
+    fn part1(&self, schematic: &Schematic) -> Sum {
+        parts_sum(schematic, 0..schematic.rows)
+    }
+
+    fn part2(&self, schematic: &Schematic) -> Sum {
+        gear_ratio_sum(schematic, &self.gear, 0..schematic.rows)
+    }
+}
+
+impl Streaming for Solver {
+    type Record = Row;
+    type State1 = Window;
+    type State2 = Window;
+
+    fn record(&self, line: parse::Line) -> Result<Row, ParseError> {
+        read_row(line)
+    }
+
+    fn add1(&self, window: &mut Window, row: &Row) {
+        window.push(row, parts_sum)
+    }
+
+    fn add2(&self, window: &mut Window, row: &Row) {
+        window.push(row, |s, rows| gear_ratio_sum(s, &self.gear, rows))
+    }
+
+    fn answer1(&self, window: Window) -> Sum {
+        window.finish(parts_sum)
+    }
+
+    fn answer2(&self, window: Window) -> Sum {
+        window.finish(|s, rows| gear_ratio_sum(s, &self.gear, rows))
+    }
+}
+
+// One line per number, with its position as line:column.
+fn listing<'a>(label: &str, numbers: impl Iterator<Item = &'a Number>) -> String {
+    numbers.map(|n| format!("{}: {} @ {}:{}\n", label, n.value, n.row + 1, n.start + 1)).collect()
+}
+
+// The queries and the graph need the whole schematic before the answers.
+fn wants_schematic(args: &Args) -> bool {
+    args.flag("loners") || ["touching", "graph"].iter().any(|o| matches!(args.value::<String>(o), Ok(Some(_))))
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let solver = Solver::from(args)?;
+    if wants_schematic(args) {
+        let schematic = solver.parse(input)?;
+        let touching = args.value::<String>("touching")?;
+        if (touching.is_some() || args.flag("loners")) && args.format != Format::Text {
+            return Err(UsageError(String::from("--touching and --loners need --format text")).into())
+        }
+        if let Some(symbols) = touching {
+            print!("{}", listing(&format!("next to {}", symbols), schematic.touching(&symbols)));
+        }
+        if args.flag("loners") {
+            print!("{}", listing("next to none", schematic.loners()));
+        }
+        if let Some(file) = args.value::<String>("graph")? {
+            fs::write(&file, schematic.to_dot()).map_err(|e| format!("{}: {}", file, e))?;
+        }
+    }
+    puzzle::solve(&solver, args, input)
+}
+
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    if wants_schematic(args) {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        return solve(args, &text)
+    }
+    puzzle::solve_stream(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver::default();
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), Ok(4361));
+        assert_eq!(solver.part2(&model), Ok(467835));
+    }
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u64> {
+        numbers.map(|n| n.value).collect()
+    }
+
+    #[test]
+    fn queries() {
+        let schematic = Solver::default().parse(EXAMPLE).unwrap();
+        assert_eq!(schematic.numbers[0], Number{ value: 467, row: 0, start: 0, end: 2 });
+        assert_eq!(schematic.symbols[0], Symbol{ c: '*', row: 1, col: 3 });
+        assert_eq!(values(schematic.neighbors(0)), [467, 35]);
+        assert_eq!(values(schematic.loners()), [114, 58]);
+        assert_eq!(values(schematic.touching("#$")), [633, 664]);
+        assert_eq!(values(schematic.touching("+")), [592]);
+        let gear = Gear{ symbols: String::from("*"), arity: 1 };
+        assert_eq!(schematic.gears(&gear).map(|(s, parts)| (s.row, values(parts.into_iter()))).collect::<Vec<_>>(),
+                   [(4, vec![617])]);
+    }
+
+    #[test]
+    fn gear_options() {
+        let solver = |args: &[&str]| {
+            Solver::from(&Args::parse(args.iter().map(|a| a.to_string()), OPTIONS).unwrap())
+        };
+        let model = Solver::default().parse(EXAMPLE).unwrap();
+        assert_eq!(solver(&["--arity=1"]).unwrap().part2(&model), Ok(617));
+        assert_eq!(solver(&["--gears=*+$#", "--arity=1"]).unwrap().part2(&model), Ok(617 + 592 + 664 + 633));
+        let streamed = puzzle::stream_answers(&solver(&["--gears=*+$#", "--arity=1"]).unwrap(), &mut EXAMPLE.as_bytes());
+        assert_eq!(streamed.unwrap(), (Ok(4361), Ok(617 + 592 + 664 + 633)));
+        assert!(solver(&["--arity=0"]).is_err());
+    }
+
+    #[test]
+    fn graph() {
+        let schematic = Solver::default().parse("1*2\n..3\n").unwrap();
+        assert_eq!(schematic.to_dot(), "\
+graph schematic {
+  n0 [label=\"1 @ 1:1\"];
+  n1 [label=\"2 @ 1:3\"];
+  n2 [label=\"3 @ 2:3\"];
+  s0 [label=\"* @ 1:2\", shape=box];
+  n0 -- s0;
+  n1 -- s0;
+  n2 -- s0;
+}
+");
+    }
+
+    #[test]
+    fn streaming() {
+        let answers = |input: &str| puzzle::stream_answers(&Solver::default(), &mut input.as_bytes()).unwrap();
+        assert_eq!(answers(EXAMPLE), (Ok(4361), Ok(467835)));
+        assert_eq!(answers("1*2"), (Ok(3), Ok(2)));
+        assert_eq!(answers(""), (Ok(0), Ok(0)));
+    }
+
+    #[test]
+    fn overflow() {
+        let solver = Solver::default();
+        let (part1, part2) = puzzle::stream_answers(&solver, &mut "9999999999*9999999999".as_bytes()).unwrap();
+        assert_eq!(part1, Ok(19999999998));
+        assert_eq!(part2, Err(Overflow));
+        let e = solver.parse("..123456789012345678901234567890").err().unwrap();
+        assert_eq!((e.column, e.msg.as_str()), (3, "number too large"));
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let solver = Solver::default();
+        let gen = generate::Generator{ size: 30, ..Default::default() };
+        for seed in 0..20 {
+            let input = gen.generate(&mut Rng::new(seed));
+            let all = input.split(|c: char| !c.is_ascii_digit())
+                .filter_map(|s| s.parse::<u64>().ok())
+                .sum();
+            let model = solver.parse(&input).unwrap();
+            assert!(solver.part1(&model).unwrap() <= all);
+        }
+        let gen = generate::Generator{ size: 30, symbols: 0.0, ..Default::default() };
+        let model = solver.parse(&gen.generate(&mut Rng::new(0))).unwrap();
+        assert_eq!(solver.part1(&model), Ok(0));
+        assert_eq!(solver.part2(&model), Ok(0));
+    }
+}
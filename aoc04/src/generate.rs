@@ -0,0 +1,68 @@
+// Random inputs: scratchcards with their winning numbers and the
+// numbers we have.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("cards", "n", "number of cards (default 200)"),
+    Opt::value("winning", "n", "number of winning numbers per card (default 10)"),
+    Opt::value("have", "n", "number of numbers we have per card (default 25)"),
+];
+
+pub struct Generator {
+    pub cards: usize,
+    pub winning: usize,
+    pub have: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ cards: 200, winning: 10, have: 25 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            cards: args.value_in("cards", d.cards, 1..)?,
+            winning: args.value_in("winning", d.winning, 1..=25)?,
+            have: args.value_in("have", d.have, 1..)?,
+        })
+    }
+
+    // Cards never win copies past the end of the table.  Copies multiply
+    // quickly, so once a card has too many of them, it wins nothing,
+    // which keeps the total number of cards within u32.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let pool = 99.max(self.winning + self.have);
+        let cap = u32::MAX as u64 / (self.cards as u64 * (self.winning as u64 + 1));
+        let mut copies = vec![1u64; self.cards];
+        let mut out = String::new();
+        for i in 0..self.cards {
+            let mut matches = rng.index(self.winning.min(self.have) + 1).min(self.cards - 1 - i);
+            if copies[i] > cap {
+                matches = 0;
+            }
+            for j in i + 1..=i + matches {
+                copies[j] += copies[i];
+            }
+            let mut numbers = (1..=pool).collect::<Vec<_>>();
+            rng.shuffle(&mut numbers);
+            let (winning, others) = numbers.split_at(self.winning);
+            let mut have = winning[..matches].iter()
+                .chain(&others[..self.have - matches])
+                .copied()
+                .collect::<Vec<_>>();
+            rng.shuffle(&mut have);
+            let show = |ns: &[usize]| ns.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>().join(" ");
+            out += &format!("Card {:>3}: {} | {}\n", i + 1, show(winning), show(&have));
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
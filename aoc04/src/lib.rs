@@ -0,0 +1,386 @@
+// Advent-of-Code 2023
+// Day 04
+// Author: Matthias Blume
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Range;
+
+use util::bench::{self, BenchResult};
+use util::bigint::{BigUint, BIG};
+use util::cli::{Args, Format, Opt, RunResult, UsageError};
+use util::iter::*;
+use util::parse::{self, Line, ParseError};
+use util::math::{self, Count, Overflow, Total};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming, ToAnswer};
+
+pub mod generate;
+
+pub const DAY: usize = 4;
+
+pub const OPTIONS: &[Opt] = &[
+    BIG,
+    Opt::flag("cards", "show the points of each card, the cards it wins, and its copies before the answers"),
+];
+
+// A card is represented by its number and how many of the
+// numbers we have are winning numbers.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Card {
+    pub number: u32,
+    pub matches: u32,
+}
+
+fn read_numbers<T: FromIterator<u32>>(line: &Line, input: &str) -> Result<T, ParseError> {
+    input.split_whitespace().map(|s| line.parse(s, "number")).collect()
+}
+
+fn read_card(line: Line) -> Result<Card, ParseError> {
+    let (name_str, data_str) = line.text.split_once(':')
+        .ok_or_else(|| line.fail("missing ':'"))?;
+    let number: u32 = match name_str.split_whitespace().boxed()[..] {
+        ["Card", number_str] => match line.parse(number_str, "card number")? {
+            0 => return Err(line.error(number_str, "cards are numbered from 1")),
+            n => n,
+        },
+        _ => return Err(line.error(name_str, "malformed Card spec")),
+    };
+    let (winning_str, have_str) = data_str.split_once('|')
+        .ok_or_else(|| line.error(data_str, "missing '|'"))?;
+    let winning: HashSet<u32> = read_numbers(&line, winning_str)?;
+    let have: Vec<u32> = read_numbers(&line, have_str)?;
+    let matches = have.iter().filter(|h| winning.contains(h)).count() as u32;
+    Ok(Card{ number, matches })
+}
+
+// Checks that the cards, which may come in any order, are numbered
+// consecutively from the given number on, each exactly once, and puts
+// them in order.  Each card comes with its line, for the errors.
+fn check_table(mut cards: Vec<(Card, usize)>, first: u32) -> Result<Vec<Card>, ParseError> {
+    // Stable, so that of two cards with the same number the first one
+    // in the input comes first.
+    cards.sort_by_key(|(c, _)| c.number);
+    let mut expected = first;
+    for (i, (card, line)) in cards.iter().enumerate() {
+        let error = |msg: String| Err(ParseError::new(*line, 1, "", &msg));
+        match cards[..i].last() {
+            Some((prev, first_line)) if prev.number == card.number =>
+                return error(format!("duplicate card {} (first on line {})", card.number, first_line)),
+            _ if card.number < expected => return error(format!("duplicate card {}", card.number)),
+            _ if card.number > expected =>
+                return error(format!("missing card {} (before card {})", expected, card.number)),
+            _ => expected += 1,
+        }
+    }
+    Ok(cards.into_iter().map(|(c, _)| c).collect())
+}
+
+fn read_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    let cards = parse::lines(input)
+        .map(|line| Ok((read_card(line)?, line.number)))
+        .collect::<Result<_, ParseError>>()?;
+    check_table(cards, 1)
+}
+
+fn points<N: Count>(card: &Card) -> Result<N, Overflow> {
+    if card.matches == 0 { return Ok(N::from_u64(0)) }
+    (1..card.matches).try_fold(N::from_u64(1), |p, _| p.plus(&p))
+}
+
+// The places in the table of the cards that the given card wins: those
+// right after it, as many as it has matches, but none past the end of
+// the table.
+fn won(card: &Card, cards: usize) -> Range<usize> {
+    let first = card.number as usize;
+    first..first.saturating_add(card.matches as usize).min(cards).max(first)
+}
+
+// Returns the number of copies of the given card that we end up with:
+// the original and those won by earlier cards, which come first among
+// the pending ones.  Its own copies then win copies of the cards after
+// it.  Copies double with every card that wins the next one, so they
+// quickly outgrow any fixed-size integer.  Copies won for cards past the
+// end of the table stay pending and never count.
+fn collect_card<N: Count>(card: &Card, pending: &mut VecDeque<N>) -> Result<N, Overflow> {
+    let copies = pending.pop_front().unwrap_or(N::from_u64(0)).plus(&N::from_u64(1))?;
+    for i in 0..card.matches as usize {
+        if i == pending.len() {
+            pending.push_back(N::from_u64(0));
+        }
+        pending[i] = pending[i].plus(&copies)?;
+    }
+    Ok(copies)
+}
+
+// Cards as they stream by, counted with the given function: those that
+// come in order right away, and the others, from the first one out of
+// order on, as a table once all are in.
+pub struct Cards<N> {
+    sum: Total<N>,
+    pending: VecDeque<N>,
+    next: u32,
+    rest: Vec<(Card, usize)>,
+}
+
+impl<N: Count> Default for Cards<N> {
+    fn default() -> Self {
+        Cards{ sum: Total::default(), pending: VecDeque::new(), next: 1, rest: Vec::new() }
+    }
+}
+
+type CountFn<N> = fn(&Card, &mut VecDeque<N>) -> Result<N, Overflow>;
+
+impl<N: Count> Cards<N> {
+    fn add(&mut self, (card, line): &(Card, usize), count: CountFn<N>) {
+        if self.rest.is_empty() && card.number == self.next {
+            self.sum.add(count(card, &mut self.pending));
+            self.next += 1;
+        } else {
+            self.rest.push((card.clone(), *line));
+        }
+    }
+
+    fn finish(&mut self, count: CountFn<N>) -> Result<(), ParseError> {
+        for card in check_table(mem::take(&mut self.rest), self.next)? {
+            self.sum.add(count(&card, &mut self.pending));
+        }
+        Ok(())
+    }
+}
+
+// Counts in N, which is u64 unless asked for big integers.
+pub struct Solver<N>(PhantomData<N>);
+
+impl<N> Default for Solver<N> {
+    fn default() -> Self {
+        Solver(PhantomData)
+    }
+}
+
+impl<N: Count + ToAnswer> Puzzle for Solver<N> {
+    type Model = Vec<Card>;
+    type Answer1 = Result<N, Overflow>;
+    type Answer2 = Result<N, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Card>, ParseError> {
+        read_cards(input)
+    }
+
+    fn part1(&self, cards: &Vec<Card>) -> Result<N, Overflow> {
+        math::sum(cards.iter().map(points))
+    }
+
+    fn part2(&self, cards: &Vec<Card>) -> Result<N, Overflow> {
+        let mut pending = VecDeque::new();
+        math::sum(cards.iter().map(|c| collect_card(c, &mut pending)))
+    }
+}
+
+// Part 2 only keeps the copies won for cards still to come, as long as
+// the cards come in order.
+impl<N: Count + ToAnswer> Streaming for Solver<N> {
+    type Record = (Card, usize);
+    type State1 = Cards<N>;
+    type State2 = Cards<N>;
+
+    fn record(&self, line: Line) -> Result<(Card, usize), ParseError> {
+        Ok((read_card(line)?, line.number))
+    }
+
+    fn add1(&self, cards: &mut Cards<N>, card: &(Card, usize)) {
+        cards.add(card, |c, _| points(c))
+    }
+
+    fn add2(&self, cards: &mut Cards<N>, card: &(Card, usize)) {
+        cards.add(card, collect_card)
+    }
+
+    fn finish(&self, cards1: &mut Cards<N>, cards2: &mut Cards<N>) -> Result<(), ParseError> {
+        cards1.finish(|c, _| points(c))?;
+        cards2.finish(collect_card)
+    }
+
+    fn answer1(&self, cards: Cards<N>) -> Result<N, Overflow> {
+        cards.sum.get()
+    }
+
+    fn answer2(&self, cards: Cards<N>) -> Result<N, Overflow> {
+        cards.sum.get()
+    }
+}
+
+// One row per card: its points (part 1), and the cards it wins and the
+// copies of it that we end up with (part 2).
+fn report<N: Count + fmt::Display>(cards: &[Card]) -> Result<String, Overflow> {
+    let mut pending = VecDeque::new();
+    let mut report = String::new();
+    for card in cards {
+        let copies = collect_card::<N>(card, &mut pending)?;
+        let won = won(card, cards.len());
+        let wins = match won.len() {
+            0 => String::from("nothing"),
+            1 => format!("card {}", won.start + 1),
+            _ => format!("cards {} to {}", won.start + 1, won.end),
+        };
+        report.push_str(&format!("Card {}: matching {}, points {}, wins {}, copies {}\n",
+                                 card.number, card.matches, points::<N>(card)?, wins, copies));
+    }
+    Ok(report)
+}
+
+fn solve_in<N: Count + ToAnswer + fmt::Display>(args: &Args, input: &str) -> SolveResult {
+    let solver = Solver::<N>::default();
+    if args.flag("cards") {
+        if args.format != Format::Text {
+            return Err(UsageError(String::from("--cards needs --format text")).into())
+        }
+        print!("{}", report::<N>(&solver.parse(input)?)?);
+    }
+    puzzle::solve(&solver, args, input)
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    if args.flag(BIG.name) {
+        solve_in::<BigUint>(args, input)
+    } else {
+        solve_in::<u64>(args, input)
+    }
+}
+
+// The report needs the whole table, so it does without streaming.
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    if args.flag("cards") {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        return solve(args, &text)
+    }
+    if args.flag(BIG.name) {
+        puzzle::solve_stream(&Solver::<BigUint>::default(), args, input)
+    } else {
+        puzzle::solve_stream(&Solver::<u64>::default(), args, input)
+    }
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    if args.flag(BIG.name) {
+        bench::measure(&Solver::<BigUint>::default(), args, input, runs)
+    } else {
+        bench::measure(&Solver::<u64>::default(), args, input, runs)
+    }
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver::<u64>::default();
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), Ok(13));
+        assert_eq!(solver.part2(&model), Ok(30));
+    }
+
+    #[test]
+    fn streaming() {
+        let answers = puzzle::stream_answers(&Solver::<u64>::default(), &mut EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(answers, (Ok(13), Ok(30)));
+    }
+
+    // Once a card comes out of order, the rest are checked and counted
+    // as a table.
+    #[test]
+    fn streaming_out_of_order() {
+        let stream = |input: &str| puzzle::stream_answers(&Solver::<u64>::default(), &mut input.as_bytes());
+        let mut lines = EXAMPLE.lines().collect::<Vec<_>>();
+        lines.swap(2, 4);
+        assert_eq!(stream(&lines.join("\n")).unwrap(), (Ok(13), Ok(30)));
+        let error = |input: &str| *stream(input).err().unwrap().downcast::<ParseError>().unwrap();
+        let e = error("Card 1: 1 | 1\nCard 2: 2 | 3\nCard 1: 4 | 5\n");
+        assert_eq!((e.line, e.msg.as_str()), (3, "duplicate card 1"));
+        let e = error("Card 1: 1 | 1\nCard 4: 2 | 3\nCard 2: 4 | 5\n");
+        assert_eq!((e.line, e.msg.as_str()), (2, "missing card 3 (before card 4)"));
+    }
+
+    // Cards can come in any order, but none may be missing or repeated.
+    #[test]
+    fn validation() {
+        let solver = Solver::<u64>::default();
+        let mut lines = EXAMPLE.lines().collect::<Vec<_>>();
+        lines.reverse();
+        let model = solver.parse(&lines.join("\n")).unwrap();
+        assert_eq!(model[0], Card{ number: 1, matches: 4 });
+        assert_eq!(solver.part2(&model), Ok(30));
+        let e = solver.parse("Card 1: 1 | 1\nCard 2: 2 | 3\nCard 1: 4 | 5\n").err().unwrap();
+        assert_eq!((e.line, e.msg.as_str()), (3, "duplicate card 1 (first on line 1)"));
+        let e = solver.parse("Card 4: 1 | 1\nCard 1: 2 | 3\nCard 2: 4 | 5\n").err().unwrap();
+        assert_eq!((e.line, e.msg.as_str()), (1, "missing card 3 (before card 4)"));
+        let e = solver.parse("Card 0: 1 | 1\n").err().unwrap();
+        assert_eq!((e.column, e.msg.as_str()), (6, "cards are numbered from 1"));
+    }
+
+    #[test]
+    fn cards_report() {
+        let cards = Solver::<u64>::default().parse(EXAMPLE).unwrap();
+        assert_eq!(report::<u64>(&cards).unwrap(), "\
+Card 1: matching 4, points 8, wins cards 2 to 5, copies 1
+Card 2: matching 2, points 2, wins cards 3 to 4, copies 2
+Card 3: matching 2, points 2, wins cards 4 to 5, copies 4
+Card 4: matching 1, points 1, wins card 5, copies 8
+Card 5: matching 0, points 0, wins nothing, copies 14
+Card 6: matching 0, points 0, wins nothing, copies 1
+");
+    }
+
+    // Wins past the end of the table do not count.
+    #[test]
+    fn wins_past_the_end() {
+        let cards = [Card{ number: 1, matches: 5 }, Card{ number: 2, matches: 1 }];
+        assert_eq!(won(&cards[0], 2), 1..2);
+        assert_eq!(won(&cards[1], 2), 2..2);
+        assert_eq!(Solver::<u64>::default().part2(&cards.to_vec()), Ok(3));
+    }
+
+    // When every card wins all later ones, the copies double from card
+    // to card: 2^70 - 1 in all.
+    #[test]
+    fn copies_overflow() {
+        let cards = (1..=70).map(|number| Card{ number, matches: 70 - number }).collect::<Vec<_>>();
+        assert_eq!(Solver::<u64>::default().part2(&cards), Err(Overflow));
+        let copies = Solver::<BigUint>::default().part2(&cards).unwrap();
+        assert_eq!(copies.to_string(), "1180591620717411303423");
+    }
+
+    // Every card counts at least once, and the copies never overflow.
+    #[test]
+    fn generated_inputs() {
+        for cards in [1, 10, 200, 1000] {
+            let gen = generate::Generator{ cards, ..Default::default() };
+            let solver = Solver::<u64>::default();
+            for seed in 0..5 {
+                let model = solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+                assert_eq!(model.len(), cards);
+                assert!(solver.part1(&model).unwrap() <= cards as u64 * (1 << (gen.winning - 1)));
+                assert!(solver.part2(&model).unwrap() >= cards as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Random inputs: seed ranges and the chain of maps from seeds to
+// locations.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("seeds", "n", "number of seed ranges (default 10)"),
+    Opt::value("ranges", "n", "maximum number of ranges per map (default 30)"),
+    Opt::value("max", "n", "bound on all numbers (default 4294967296)"),
+];
+
+const KINDS: [&str; 8] =
+    ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+
+pub struct Generator {
+    pub seeds: usize,
+    pub ranges: usize,
+    pub max: i64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ seeds: 10, ranges: 30, max: 1 << 32 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let seeds = args.value_in("seeds", d.seeds, 1..)?;
+        Ok(Generator{
+            seeds,
+            ranges: args.value_in("ranges", d.ranges, 1..)?,
+            max: args.value_in("max", d.max, 2 * seeds as i64..=1 << 48)?,
+        })
+    }
+
+    // The source ranges of a map are disjoint, but they need not cover
+    // everything, and the destinations may overlap.
+    fn map(&self, rng: &mut Rng) -> Vec<(i64, i64, i64)> {
+        let mut cuts = (0..rng.index(self.ranges) * 2 + 2)
+            .map(|_| rng.range(0..=self.max))
+            .collect::<Vec<_>>();
+        cuts.sort();
+        cuts.dedup();
+        cuts.chunks_exact(2)
+            .map(|c| {
+                let len = c[1] - c[0];
+                (rng.range(0..=self.max - len), c[0], len)
+            })
+            .collect()
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let spread = self.max / self.seeds as i64;
+        let seeds = (0..self.seeds)
+            .map(|_| {
+                let len = rng.range(1..=spread / 2);
+                format!("{} {}", rng.range(0..=self.max - len), len)
+            })
+            .collect::<Vec<_>>();
+        let mut out = format!("seeds: {}\n", seeds.join(" "));
+        for kinds in KINDS.windows(2) {
+            out += &format!("\n{}-to-{} map:\n", kinds[0], kinds[1]);
+            for (d, s, l) in self.map(rng) {
+                out += &format!("{} {} {}\n", d, s, l);
+            }
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
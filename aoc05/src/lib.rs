@@ -0,0 +1,208 @@
+// Advent-of-Code 2023
+// Day 05
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::interval::{Interval, IntervalSet};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 5;
+
+pub const OPTIONS: &[Opt] = &[];
+
+// Values in the source interval get moved by the given shift.
+struct RangeMap {
+    src: Interval,
+    shift: i64,
+}
+
+// Reads seed values individually (part 1).
+fn single_seeds(spec: &[i64]) -> IntervalSet {
+    IntervalSet::from_intervals(spec.iter().map(|&s| Interval::with_len(s, 1)))
+}
+
+// Reads seed values in pairs (start, len) (part 2).
+fn seeds(spec: &[i64]) -> IntervalSet {
+    IntervalSet::from_intervals(spec.chunks(2).map(|c| Interval::with_len(c[0], c[1])))
+}
+
+// Applies the mapping to all values in the given set, resulting in
+// a new set.  Values outside all of the source ranges stay the same.
+fn apply_mapping(cur: &IntervalSet, mapping: &[RangeMap]) -> IntervalSet {
+    let mut result = IntervalSet::new();
+    let mut unmapped = cur.clone();
+    for rm in mapping {
+        let src = IntervalSet::from(rm.src);
+        result = result.union(&cur.intersection(&src).shift(rm.shift));
+        unmapped = unmapped.difference(&src);
+    }
+    result.union(&unmapped)
+}
+
+// Upon seeing a new map type, checks that the old kind matches the
+// map's source.  Then returns the new kind.
+fn changed_kind(line: &Line, kind: String, map_type: &str) -> Result<String, ParseError> {
+    match map_type.split('-').boxed()[..] {
+        [from, "to", to] => {
+            if kind != from {
+                return Err(line.error(from, &format!("wrong transition for {}", kind)))
+            }
+            Ok(String::from(to))
+        },
+        _ => Err(line.error(map_type, "bad map type")),
+    }
+}
+
+// The seed numbers and the chain of mappings.
+pub struct Almanac {
+    seeds: Vec<i64>,
+    mappings: Vec<Vec<RangeMap>>,
+}
+
+impl Almanac {
+    fn lowest(&self, seeds: IntervalSet) -> i64 {
+        self.mappings.iter()
+            .fold(seeds, |cur, mapping| apply_mapping(&cur, mapping))
+            .min().unwrap_or(0)
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Almanac;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Almanac, ParseError> {
+        let mut seeds = Vec::new();
+        let mut mappings = Vec::new();
+        let mut mapping = Vec::new();
+        let mut kind = String::from("unknown kind");
+
+        for line in parse::lines(input) {
+            let number = |s| line.parse(s, "number");
+            match &line.text.split_whitespace().boxed()[..] {
+                ["seeds:", seeds_strings @ ..] => {
+                    kind = String::from("seed");
+                    seeds = seeds_strings.iter().map(|s| number(s)).collect::<Result<_, _>>()?;
+                    if seeds.len() % 2 != 0 {
+                        return Err(line.fail("odd number of seed values"))
+                    }
+                },
+                [map_type, "map:"] => {
+                    mappings.push(mapping);
+                    mapping = Vec::new();
+                    kind = changed_kind(&line, kind, map_type)?
+                },
+                [d, s, l] => {
+                    let (d, s) = (number(d)?, number(s)?);
+                    mapping.push(RangeMap{ src: Interval::with_len(s, number(l)?), shift: d - s })
+                },
+                [] => (),
+                _ => return Err(line.fail("invalid input")),
+            }
+        }
+        mappings.push(mapping);
+        if kind != "location" {
+            return Err(parse::eof_error(input, &format!("mappings end at {} instead of location", kind)))
+        }
+        Ok(Almanac{ seeds, mappings })
+    }
+
+    fn part1(&self, almanac: &Almanac) -> i64 {
+        almanac.lowest(single_seeds(&almanac.seeds))
+    }
+
+    fn part2(&self, almanac: &Almanac) -> i64 {
+        almanac.lowest(seeds(&almanac.seeds))
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 35);
+        assert_eq!(Solver.part2(&model), 46);
+    }
+
+    // The location of a single seed, one map range at a time.
+    fn location(almanac: &Almanac, seed: i64) -> i64 {
+        almanac.mappings.iter().fold(seed, |x, mapping| {
+            mapping.iter().find(|rm| rm.src.contains(x)).map_or(x, |rm| x + rm.shift)
+        })
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ seeds: 4, ranges: 5, max: 1000 };
+        for seed in 0..20 {
+            let almanac = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let singles = almanac.seeds.iter().map(|&s| location(&almanac, s)).min();
+            assert_eq!(Some(Solver.part1(&almanac)), singles);
+            let ranges = almanac.seeds.chunks(2)
+                .flat_map(|c| c[0]..c[0] + c[1])
+                .map(|s| location(&almanac, s))
+                .min();
+            assert_eq!(Some(Solver.part2(&almanac)), ranges);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Random inputs: race times and record distances.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("races", "n", "number of races (default 4)"),
+    Opt::value("time", "n", "maximum time of a race (default 100)"),
+];
+
+pub struct Generator {
+    pub races: usize,
+    pub time: u64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ races: 4, time: 100 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            races: args.value_in("races", d.races, 1..=6)?,
+            time: args.value_in("time", d.time, 3..=1000)?,
+        })
+    }
+
+    // Every record can be beaten, also when the numbers are read as one
+    // (part 2).  Times are at least 3, so that there is room for that.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        loop {
+            let mut times = Vec::new();
+            let mut distances = Vec::new();
+            for _ in 0..self.races {
+                let t = rng.range(3..=self.time as i64);
+                times.push(t.to_string());
+                distances.push(rng.range(1..=t * t / 4 - 1).to_string());
+            }
+            let t = times.concat().parse::<u128>().unwrap();
+            let d = distances.concat().parse::<u128>().unwrap();
+            if d < t * t / 4 {
+                let width = times.iter().chain(&distances).map(String::len).max().unwrap_or(0);
+                let show = |ns: &[String]| ns.iter().map(|n| format!(" {:>w$}", n, w = width + 2)).collect::<String>();
+                return format!("Time:    {}\nDistance:{}\n", show(&times), show(&distances))
+            }
+        }
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
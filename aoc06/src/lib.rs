@@ -0,0 +1,162 @@
+// Advent-of-Code 2023
+// Day 06
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::math::{Count, Overflow};
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 6;
+
+pub const OPTIONS: &[Opt] = &[];
+
+// Holding the button for h out of t milliseconds goes h * (t - h)
+// millimeters, which grows until h reaches t / 2 and then shrinks again
+// symmetrically.  So the winning h are those between the first one that
+// beats the record and its mirror image.  The first one is found by
+// binary search, in exact integer arithmetic; distances too large for
+// a u128 certainly beat the record.
+fn num_winning_inputs(time_limit: u128, previous_max: u128) -> u128 {
+    let beats = |h: u128| h.checked_mul(time_limit - h).is_none_or(|d| d > previous_max);
+    let (mut low, mut high) = (0, time_limit / 2);
+    if !beats(high) { return 0 }
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if beats(mid) { high = mid } else { low = mid + 1 }
+    }
+    time_limit - 2 * low + 1
+}
+
+// Reads the numbers on a line.  For part 2 the spaces between them
+// are ignored, so that they make up a single number, which might be
+// too large even for a u128.
+fn numbers(strs: &[String], part: Part) -> Result<Vec<u128>, Overflow> {
+    let number = |s: &str| s.parse().map_err(|_| Overflow);
+    match part {
+        Part::One => strs.iter().map(|s| number(s)).collect(),
+        Part::Two => Ok(vec![number(&strs.concat())?]),
+    }
+}
+
+// The number strings of the "Time:" and "Distance:" lines.
+pub struct Races {
+    times: Vec<String>,
+    distances: Vec<String>,
+}
+
+impl Races {
+    fn solve(&self, part: Part) -> Result<u64, Overflow> {
+        let times = numbers(&self.times, part)?;
+        let distances = numbers(&self.distances, part)?;
+        times.iter().zip(distances.iter())
+            .map(|(&t, &d)| u64::try_from(num_winning_inputs(t, d)).map_err(|_| Overflow))
+            .try_fold(1, |product, n| product.times(&n?))
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Races;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Races, ParseError> {
+        let mut races = Races{ times: Vec::new(), distances: Vec::new() };
+        for line in parse::lines(input) {
+            // Numbers are kept as strings, but they must be valid.
+            let to_strings = |strs: &[&str]| strs.iter()
+                .map(|s| line.parse::<u64>(s, "number").map(|_| s.to_string()))
+                .collect::<Result<_, _>>();
+            match &line.text.split_whitespace().boxed()[..] {
+                ["Time:", times_str @ ..] =>
+                    races.times = to_strings(times_str)?,
+                ["Distance:", distances_str @ ..] =>
+                    races.distances = to_strings(distances_str)?,
+                _ => return Err(line.fail("invalid input")),
+            }
+        }
+        if races.times.len() != races.distances.len() {
+            return Err(parse::eof_error(input, "times and distances do not match up"))
+        }
+        Ok(races)
+    }
+
+    fn part1(&self, races: &Races) -> Result<u64, Overflow> {
+        races.solve(Part::One)
+    }
+
+    fn part2(&self, races: &Races) -> Result<u64, Overflow> {
+        races.solve(Part::Two)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), Ok(288));
+        assert_eq!(Solver.part2(&model), Ok(71503));
+    }
+
+    // Distances near the limits of 64 and 128 bits, where floating point
+    // gets the answer wrong.
+    #[test]
+    fn large_races() {
+        assert_eq!(num_winning_inputs(u64::MAX as u128, 0), u64::MAX as u128 - 1);
+        assert_eq!(num_winning_inputs(10, 20), 5);
+        assert_eq!(num_winning_inputs(10, 24), 1);
+        assert_eq!(num_winning_inputs(10, 25), 0);
+        let t = 1u128 << 63;
+        assert_eq!(num_winning_inputs(t, (t / 2) * (t / 2) - 1), 1);
+        assert_eq!(num_winning_inputs(t, (t / 2 - 1) * (t / 2 + 1)), 1);
+        assert_eq!(num_winning_inputs(t, (t / 2 - 1) * (t / 2 + 1) - 1), 3);
+        let model = Solver.parse("Time: 18446744073709551615 99\nDistance: 0 0\n").unwrap();
+        assert_eq!(Solver.part2(&model), Err(Overflow));
+    }
+
+    // Tries every way of holding the button.
+    fn ways(time: u64, record: u64) -> u64 {
+        (0..=time).filter(|h| h * (time - h) > record).count() as u64
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator::default();
+        for seed in 0..20 {
+            let races = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let number = |s: &String| s.parse::<u64>().unwrap();
+            let expected = races.times.iter().zip(&races.distances)
+                .map(|(t, d)| ways(number(t), number(d)))
+                .product::<u64>();
+            assert_eq!(Solver.part1(&races), Ok(expected));
+            assert!(Solver.part2(&races).unwrap() >= 1);
+        }
+    }
+}
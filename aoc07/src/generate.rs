@@ -0,0 +1,56 @@
+// Random inputs: distinct hands of cards with their bids.
+
+use std::collections::HashSet;
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("hands", "n", "number of hands, if there are that many distinct ones (default 1000)"),
+    Opt::value("jokers", "p", "chance of each card being a J (default 0.08)"),
+];
+
+const CARDS: &[u8] = b"23456789TQKA";
+
+pub struct Generator {
+    pub hands: usize,
+    pub jokers: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ hands: 1000, jokers: 0.08 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            hands: args.value_in("hands", d.hands, 1..)?,
+            jokers: args.value_in("jokers", d.jokers, 0.0..=1.0)?,
+        })
+    }
+
+    // Hands must be distinct for the ranking to be well-defined.  With
+    // lots of jokers there may not be enough of them, so after a while
+    // this settles for fewer.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut seen = HashSet::new();
+        let mut out = String::new();
+        for _ in 0..100 * self.hands {
+            if seen.len() == self.hands { break }
+            let hand = (0..5)
+                .map(|_| if rng.chance(self.jokers) { 'J' } else { rng.choose(CARDS) as char })
+                .collect::<String>();
+            if seen.insert(hand.clone()) {
+                out += &format!("{} {}\n", hand, rng.range(1..=1000));
+            }
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
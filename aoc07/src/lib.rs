@@ -0,0 +1,314 @@
+// Advent-of-Code 2023
+// Day 07
+// Author: Matthias Blume
+
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 7;
+
+pub const OPTIONS: &[Opt] = &[];
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, Copy, Hash)]
+enum CardType {
+    CJoker,
+    C2,
+    C3,
+    C4,
+    C5,
+    C6,
+    C7,
+    C8,
+    C9,
+    CT,
+    CJ,
+    CQ,
+    CK,
+    CA,
+}
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Hand(HandType, CardType, CardType, CardType, CardType, CardType);
+
+fn card_type(c: char, treat_j_as_joker: bool) -> CardType {
+    match c {
+        '2' => CardType::C2,
+        '3' => CardType::C3,
+        '4' => CardType::C4,
+        '5' => CardType::C5,
+        '6' => CardType::C6,
+        '7' => CardType::C7,
+        '8' => CardType::C8,
+        '9' => CardType::C9,
+        'T' => CardType::CT,
+        'J' if treat_j_as_joker => CardType::CJoker,
+        'J' => CardType::CJ,
+        'Q' => CardType::CQ,
+        'K' => CardType::CK,
+        'A' => CardType::CA,
+        _ => panic!("bad card"),
+    }
+}
+
+fn hand_type(mut types: Vec<CardType>) -> HandType {
+    let jokers = types.iter().filter(|&t| t == &CardType::CJoker).count();
+    types.sort();
+    let mut counts = Vec::new();
+    let mut n = 1;
+    let mut last = types[0];
+    for &cur in &types[1..5] {
+        if cur == last {
+            n += 1;
+        } else {
+            counts.push(n);
+            n = 1;
+            last = cur;
+        }
+    }
+    counts.push(n);
+    counts.sort();
+    match (counts.as_slice(), jokers) {
+        ([5], _) => HandType::FiveOfAKind,
+        ([1, 4], 0) => HandType::FourOfAKind,
+        ([1, 4], _) => HandType::FiveOfAKind,
+        ([2, 3], 0) => HandType::FullHouse,
+        ([2, 3], _) => HandType::FiveOfAKind,
+        ([1, 1, 3], 0) => HandType::ThreeOfAKind,
+        ([1, 1, 3], _) => HandType::FourOfAKind,
+        ([1, 2, 2], 0) => HandType::TwoPair,
+        ([1, 2, 2], 1) => HandType::FullHouse,
+        ([1, 2, 2], 2) => HandType::FourOfAKind,
+        ([1, 1, 1, 2], 0) => HandType::OnePair,
+        ([1, 1, 1, 2], _) => HandType::ThreeOfAKind,
+        ([1, 1, 1, 1, 1], 0) => HandType::HighCard,
+        ([1, 1, 1, 1, 1], 1) => HandType::OnePair,
+        _ => panic!("bad card counts {:?} {:?}", counts, types),
+    }
+}
+
+fn hand(s: &str, treat_j_as_joker: bool) -> Hand {
+    if s.len() != 5 { panic!("hand of wrong size") }
+    let c1 = card_type(s.chars().next().unwrap(), treat_j_as_joker);
+    let c2 = card_type(s.chars().nth(1).unwrap(), treat_j_as_joker);
+    let c3 = card_type(s.chars().nth(2).unwrap(), treat_j_as_joker);
+    let c4 = card_type(s.chars().nth(3).unwrap(), treat_j_as_joker);
+    let c5 = card_type(s.chars().nth(4).unwrap(), treat_j_as_joker);
+
+    Hand(hand_type(Vec::from([c1, c2, c3, c4, c5])), c1, c2, c3, c4, c5)
+}
+    
+
+// Checks the hand, but keeps it as a string.
+fn read_hand(line: &Line, s: &str) -> Result<String, ParseError> {
+    if s.chars().count() != 5 {
+        return Err(line.error(s, "hand of wrong size"))
+    }
+    if let Some((i, c)) = s.char_indices().find(|&(_, c)| !"23456789TJQKA".contains(c)) {
+        return Err(line.error(&s[i..i + c.len_utf8()], "bad card"))
+    }
+    Ok(s.to_string())
+}
+
+fn read_hand_bid(line: Line) -> Result<(String, u64), ParseError> {
+    match line.text.split_whitespace().boxed()[..] {
+        [h, b] => Ok((read_hand(&line, h)?, line.parse(b, "bid")?)),
+        _ => Err(line.fail("expected hand and bid")),
+    }
+}
+
+// Hands are kept as strings, since the meaning of J depends on the part.
+fn total_winnings(hand_bids: &[(String, u64)], treat_j_as_joker: bool) -> u64 {
+    let mut hand_bid_table = hand_bids.iter()
+        .map(|(h, b)| (hand(h, treat_j_as_joker), *b))
+        .collect::<Vec<_>>();
+
+    hand_bid_table.sort_by(|(x, _), (y, _)| x.cmp(y));
+
+    hand_bid_table.into_iter().enumerate().map(|(i, (_, v))| (i as u64 + 1)*v).sum()
+}
+
+// Ranking needs all hands, but equal hands can share an entry: there are
+// at most 13^5 different ones.  Equal hands rank in input order, so the
+// i-th of them (from 0) gets i more than the first.
+#[derive(Default)]
+pub struct Bids {
+    count: u64,
+    sum: u64,
+    extra: u64,  // sum of i * bid
+}
+
+fn add_bid(table: &mut HashMap<Hand, Bids>, hand: Hand, bid: u64) {
+    let bids = table.entry(hand).or_default();
+    bids.extra += bids.count * bid;
+    bids.sum += bid;
+    bids.count += 1;
+}
+
+fn table_winnings(table: HashMap<Hand, Bids>) -> u64 {
+    let mut entries = table.into_iter().collect::<Vec<_>>();
+    entries.sort_by(|(x, _), (y, _)| x.cmp(y));
+    let mut rank = 1;
+    let mut total = 0;
+    for (_, bids) in entries {
+        total += rank * bids.sum + bids.extra;
+        rank += bids.count;
+    }
+    total
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Vec<(String, u64)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse::lines(input).map(read_hand_bid).collect()
+    }
+
+    fn part1(&self, hand_bids: &Self::Model) -> u64 {
+        total_winnings(hand_bids, false)
+    }
+
+    // Part 2 treats J as a joker.
+    fn part2(&self, hand_bids: &Self::Model) -> u64 {
+        total_winnings(hand_bids, true)
+    }
+}
+
+impl Streaming for Solver {
+    type Record = (String, u64);
+    type State1 = HashMap<Hand, Bids>;
+    type State2 = HashMap<Hand, Bids>;
+
+    fn record(&self, line: Line) -> Result<Self::Record, ParseError> {
+        read_hand_bid(line)
+    }
+
+    fn add1(&self, table: &mut HashMap<Hand, Bids>, (h, bid): &Self::Record) {
+        add_bid(table, hand(h, false), *bid)
+    }
+
+    fn add2(&self, table: &mut HashMap<Hand, Bids>, (h, bid): &Self::Record) {
+        add_bid(table, hand(h, true), *bid)
+    }
+
+    fn answer1(&self, table: HashMap<Hand, Bids>) -> u64 {
+        table_winnings(table)
+    }
+
+    fn answer2(&self, table: HashMap<Hand, Bids>) -> u64 {
+        table_winnings(table)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    puzzle::solve_stream(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 6440);
+        assert_eq!(Solver.part2(&model), 5905);
+    }
+
+    // Repeated hands rank in input order, just as when sorting them all.
+    #[test]
+    fn streaming() {
+        assert_eq!(puzzle::stream_answers(&Solver, &mut EXAMPLE.as_bytes()).unwrap(), (6440, 5905));
+        let repeated = "KK677 28\n32T3K 765\nKK677 3\nKK677 400\n";
+        let model = Solver.parse(repeated).unwrap();
+        assert_eq!(puzzle::stream_answers(&Solver, &mut repeated.as_bytes()).unwrap(),
+                   (Solver.part1(&model), Solver.part2(&model)));
+    }
+
+    fn types(s: &str, treat_j_as_joker: bool) -> Vec<CardType> {
+        s.chars().map(|c| card_type(c, treat_j_as_joker)).collect()
+    }
+
+    #[test]
+    fn hand_type_without_jokers() {
+        assert_eq!(hand_type(types("AAAAA", false)), HandType::FiveOfAKind);
+        assert_eq!(hand_type(types("AA8AA", false)), HandType::FourOfAKind);
+        assert_eq!(hand_type(types("23332", false)), HandType::FullHouse);
+        assert_eq!(hand_type(types("TTT98", false)), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(types("23432", false)), HandType::TwoPair);
+        assert_eq!(hand_type(types("A23A4", false)), HandType::OnePair);
+        assert_eq!(hand_type(types("23456", false)), HandType::HighCard);
+        assert_eq!(hand_type(types("KTJJT", false)), HandType::TwoPair);
+    }
+
+    #[test]
+    fn hand_type_with_jokers() {
+        assert_eq!(hand_type(types("JJJJJ", true)), HandType::FiveOfAKind);
+        assert_eq!(hand_type(types("QJJQ2", true)), HandType::FourOfAKind);
+        assert_eq!(hand_type(types("KTJJT", true)), HandType::FourOfAKind);
+        assert_eq!(hand_type(types("2233J", true)), HandType::FullHouse);
+        assert_eq!(hand_type(types("JJ234", true)), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(types("2345J", true)), HandType::OnePair);
+    }
+
+    // Jokers only ever improve a hand, and without any J the two parts
+    // agree.
+    #[test]
+    fn generated_inputs() {
+        for jokers in [0.0, 0.3, 1.0] {
+            let gen = generate::Generator{ hands: 200, jokers };
+            for seed in 0..10 {
+                let model = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+                for (hand, _) in &model {
+                    assert!(hand_type(types(hand, true)) >= hand_type(types(hand, false)), "{}", hand);
+                }
+                if jokers == 0.0 {
+                    assert_eq!(Solver.part1(&model), Solver.part2(&model));
+                }
+            }
+        }
+    }
+}
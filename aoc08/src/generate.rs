@@ -0,0 +1,116 @@
+// Random inputs: instructions and a network in which every ghost runs
+// around a cycle of its own, reaching its goal after a prime number of
+// rounds and then again every that many rounds, as in the puzzle input.
+
+use std::collections::HashSet;
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("ghosts", "n", "number of ghosts (default 6)"),
+    Opt::value("length", "n", "maximum number of instructions (default 30)"),
+    Opt::value("rounds", "n", "maximum number of rounds to a goal (default 61)"),
+];
+
+const PRIMES: &[usize] = &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61];
+const NAME: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+pub struct Generator {
+    pub ghosts: usize,
+    pub length: usize,
+    pub rounds: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ ghosts: 6, length: 30, rounds: 61 }
+    }
+}
+
+impl Generator {
+    // The limits keep the number of nodes well below the number of
+    // possible names.
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let generator = Generator{
+            ghosts: args.value_in("ghosts", d.ghosts, 1..=8)?,
+            length: args.value_in("length", d.length, 1..=40)?,
+            rounds: args.value_in("rounds", d.rounds, 2..=61)?,
+        };
+        if generator.primes().len() < generator.ghosts {
+            return Err(UsageError(String::from("not enough distinct round counts for the ghosts")))
+        }
+        Ok(generator)
+    }
+
+    fn primes(&self) -> Vec<usize> {
+        PRIMES.iter().copied().filter(|&p| p <= self.rounds).collect()
+    }
+
+    // A fresh name whose last letter satisfies the predicate.
+    fn name(rng: &mut Rng, used: &mut HashSet<String>, last: impl Fn(u8) -> bool) -> String {
+        loop {
+            let mut name = rng.word(2, NAME);
+            name.push(loop {
+                let c = rng.choose(NAME);
+                if last(c) { break c as char }
+            });
+            if used.insert(name.clone()) { return name }
+        }
+    }
+
+    // Every step moves a ghost one place along its chain, whichever way
+    // it turns, only changing lanes.  The goal is at the end of the
+    // chain, which has as many places as there are steps in the rounds,
+    // and leads back to where the start does.
+    fn ghost(&self, rng: &mut Rng, used: &mut HashSet<String>, first: bool, len: usize,
+             rounds: usize) -> Vec<(String, String, String)> {
+        let steps = len * rounds;
+        let (start, goal) = if first {
+            (String::from("AAA"), String::from("ZZZ"))
+        } else {
+            (Self::name(rng, used, |c| c == b'A'), Self::name(rng, used, |c| c == b'Z'))
+        };
+        let lanes = (1..steps)
+            .map(|_| [0, 1].map(|_| Self::name(rng, used, |c| c != b'A' && c != b'Z')))
+            .collect::<Vec<_>>();
+        let at = |i: usize| if i == steps { [goal.clone(), goal.clone()] } else { lanes[i - 1].clone() };
+        let mut nodes = Vec::new();
+        let mut link = |node: &String, [l, r]: [String; 2]| {
+            if rng.chance(0.5) {
+                nodes.push((node.clone(), l, r))
+            } else {
+                nodes.push((node.clone(), r, l))
+            }
+        };
+        link(&start, at(1));
+        for i in 1..steps {
+            for node in &lanes[i - 1] {
+                link(node, at(i + 1));
+            }
+        }
+        link(&goal, at(1));
+        nodes
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let len = 1 + rng.index(self.length);
+        let mut primes = self.primes();
+        rng.shuffle(&mut primes);
+        let mut used = HashSet::from([String::from("AAA"), String::from("ZZZ")]);
+        let mut nodes = (0..self.ghosts)
+            .flat_map(|g| self.ghost(rng, &mut used, g == 0, len, primes[g]))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut nodes);
+        let mut out = rng.word(len, b"LR") + "\n\n";
+        for (node, l, r) in nodes {
+            out += &format!("{} = ({}, {})\n", node, l, r);
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
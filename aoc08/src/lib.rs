@@ -0,0 +1,374 @@
+// Advent-of-Code 2023
+// Day 08
+// Author: Matthias Blume
+
+use std::collections::HashMap;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::cycle;
+use util::parse::{self, Line, ParseError};
+use util::math::{self, Count, Overflow};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::iter::*;
+
+pub mod brute;
+pub mod generate;
+
+pub const DAY: usize = 8;
+
+pub const OPTIONS: &[Opt] = &[];
+
+fn step<'a>(d: char, state: &'a String, directions: &'a HashMap<String, (String, String)>) -> &'a String {
+    let (l, r) = directions.get(state).unwrap();
+    match d {
+        'L' => l,
+        'R' => r,
+        _ => panic!("bad turn")
+    }
+}
+
+fn all_steps<'a>(rl: &str, initial: &'a String, directions: &'a HashMap<String, (String, String)>) -> &'a String {
+    let mut state = initial;
+    for d in rl.chars() {
+        state = step(d, state, directions)
+    }
+    state
+}
+
+fn transition<'a>(state: &String, transitions: &'a HashMap<String, String>) -> &'a String {
+    transitions.get(state).unwrap()
+}
+
+fn ends_with(state: &str, c: char) -> bool {
+    state.chars().nth(2).unwrap() == c
+}
+
+// Follows the instructions one at a time from AAA until reaching ZZZ,
+// which can happen in the middle of a round.  There are only so many
+// pairs of a node and a place in the instructions, so a walk that takes
+// longer than that never gets there.
+fn walk(network: &Network) -> Result<u64, &'static str> {
+    let (mut state, _) = network.directions.get_key_value("AAA").ok_or("no AAA")?;
+    let mut turns = network.rl.chars().cycle();
+    for n in 0..=network.directions.len() * network.rl.len() {
+        if state == "ZZZ" { return Ok(n as u64) }
+        state = step(turns.next().unwrap(), state, &network.directions);
+    }
+    Err("no solution")
+}
+
+// The times at which a ghost is at a goal.  Since the next state only
+// depends on the current one, a ghost eventually goes around a cycle:
+// after the first prefix steps, its states repeat with the given period.
+// Goals before that come up once; those on the cycle, given by their
+// times in the first round, come up again every period.
+struct Goals {
+    prefix: i64,
+    period: i64,
+    early: Vec<i64>,
+    cyclic: Vec<i64>,
+}
+
+impl Goals {
+    fn from(initial: &String, transitions: &HashMap<String, String>) -> Self {
+        let limit = transitions.len() as u64 + 1;
+        let cycle = cycle::with_history(initial, limit, |s| transition(s, transitions), |&s| s)
+            .cycle.expect("cycle within as many steps as there are nodes");
+        let (prefix, period) = (cycle.prefix as i64, cycle.length as i64);
+        let (mut early, mut cyclic) = (Vec::new(), Vec::new());
+        let mut state = initial;
+        for t in 0..prefix + period {
+            if ends_with(state, 'Z') {
+                if t < prefix { early.push(t) } else { cyclic.push(t) }
+            }
+            state = transition(state, transitions);
+        }
+        Goals{ prefix, period, early, cyclic }
+    }
+
+    fn contains(&self, t: i64) -> bool {
+        if t < self.prefix { return self.early.contains(&t) }
+        let t = self.prefix + (t - self.prefix) % self.period;
+        self.cyclic.contains(&t)
+    }
+}
+
+// The first time at which all ghosts are at a goal, counted in rounds
+// of instructions.  Until the last ghost has reached its cycle, times
+// are checked one by one.  After that, each choice of one goal on every
+// ghost's cycle gives a system of congruences, and the answer is the
+// earliest of their solutions.
+fn first_common_goal(network: &Network) -> Option<i64> {
+    let goals = network.starts()
+        .map(|s| Goals::from(s, &network.transitions))
+        .collect::<Vec<_>>();
+    let earliest = goals.iter().map(|g| g.prefix).max()?;
+    if let Some(t) = (0..earliest).find(|&t| goals.iter().all(|g| g.contains(t))) {
+        return Some(t)
+    }
+    let mut systems = vec![Vec::new()];
+    for g in &goals {
+        systems = systems.iter()
+            .flat_map(|system| g.cyclic.iter().map(move |&t| {
+                let mut system: Vec<(i64, i64)> = system.clone();
+                system.push((t, g.period));
+                system
+            }))
+            .collect();
+    }
+    systems.into_iter()
+        .filter_map(|system| {
+            let (x, m) = math::crt(system)?;
+            if x >= earliest { return Some(x) }
+            ((earliest - x + m - 1) / m).checked_mul(m)?.checked_add(x)
+        })
+        .min()
+}
+
+// Node names consist of three letters or digits.
+fn read_node(line: &Line, node: &str) -> Result<(), ParseError> {
+    if node.len() != 3 || !node.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(line.error(node, "bad node name"))
+    }
+    Ok(())
+}
+
+// The instructions, the network itself, and for every node the node
+// reached by following all of the instructions once.
+pub struct Network {
+    rl: String,
+    directions: HashMap<String, (String, String)>,
+    transitions: HashMap<String, String>,
+}
+
+impl Network {
+    fn rllen(&self) -> u64 {
+        self.rl.len() as u64
+    }
+
+    fn starts(&self) -> impl Iterator<Item = &String> {
+        self.directions.keys().filter(|x| ends_with(x, 'A'))
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Network;
+    type Answer1 = Result<u64, &'static str>;
+    type Answer2 = Result<Result<u64, &'static str>, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Network, ParseError> {
+        let mut rl = String::from("");
+        let mut directions = HashMap::new();
+        let mut references = Vec::new();
+
+        for line in parse::lines(input) {
+            match line.text.split('=').map(str::trim).boxed()[..] {
+                [""] => (),
+                [word] => {
+                    if let Some(i) = word.find(|c| c != 'L' && c != 'R') {
+                        return Err(line.error(&word[i..], "bad turn"))
+                    }
+                    rl = word.to_string()
+                },
+                [lhs, rhs] => {
+                    let (l, r) = rhs.strip_prefix('(')
+                        .and_then(|s| s.strip_suffix(')'))
+                        .and_then(|s| s.split_once(','))
+                        .map(|(l, r)| (l.trim(), r.trim()))
+                        .ok_or_else(|| line.error(rhs, "expected (l, r)"))?;
+                    for node in [lhs, l, r] {
+                        read_node(&line, node)?;
+                    }
+                    references.extend([(line, l), (line, r)]);
+                    directions.insert(lhs.to_string(), (l.to_string(), r.to_string()));
+                },
+                _ => return Err(line.fail("bad input")),
+            }
+        }
+        if rl.is_empty() {
+            return Err(parse::eof_error(input, "no instructions"))
+        }
+        if let Some((line, node)) = references.iter().find(|(_, node)| !directions.contains_key(*node)) {
+            return Err(line.error(node, "unknown node"))
+        }
+
+        let transitions = directions
+            .iter()
+            .map(|d| (d.0.to_string(), all_steps(&rl, d.0, &directions).to_string()))
+            .collect::<HashMap<_, _>>();
+        Ok(Network{ rl, directions, transitions })
+    }
+
+    fn part1(&self, network: &Network) -> Result<u64, &'static str> {
+        walk(network)
+    }
+
+    fn part2(&self, network: &Network) -> Result<Result<u64, &'static str>, Overflow> {
+        match first_common_goal(network) {
+            Some(n) => (n as u64).times(&network.rllen()).map(Ok),
+            None => Ok(Err("no solution")),
+        }
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE1: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE2: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const EXAMPLE3: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn example1() {
+        let model = Solver.parse(EXAMPLE1).unwrap();
+        assert_eq!(Solver.part1(&model), Ok(2));
+    }
+
+    #[test]
+    fn example2() {
+        let model = Solver.parse(EXAMPLE2).unwrap();
+        assert_eq!(Solver.part1(&model), Ok(6));
+    }
+
+    #[test]
+    fn example3() {
+        let model = Solver.parse(EXAMPLE3).unwrap();
+        assert_eq!(Solver.part1(&model), Err("no AAA"));
+        assert_eq!(Solver.part2(&model), Ok(Ok(6)));
+    }
+
+    // ZZZ can come up in the middle of a round, or not at all.
+    #[test]
+    fn part1_single_steps() {
+        let model = Solver.parse("LR\n\nAAA = (ZZZ, AAA)\nZZZ = (AAA, AAA)\n").unwrap();
+        assert_eq!(Solver.part1(&model), Ok(1));
+        let model = Solver.parse("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(Solver.part1(&model), Err("no solution"));
+    }
+
+    // Both solvers give up when the ghosts are never at a goal together.
+    #[test]
+    fn no_common_goal() {
+        let model = Solver.parse("\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22C, 22C)
+").unwrap();
+        assert_eq!(Solver.part2(&model), Ok(Err("no solution")));
+        assert_eq!(brute::Solver.part2(&model), Ok(Err("no solution")));
+    }
+
+    // Goals that a ghost passes only on its way to its cycle, and more
+    // than one goal on a cycle.
+    #[test]
+    fn several_goals() {
+        let model = Solver.parse("\
+L
+
+11A = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (12Z, 12Z)
+12Z = (11D, 11D)
+11D = (11Z, 11Z)
+22A = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22Z, 22Z)
+").unwrap();
+        assert_eq!(Solver.part2(&model), Ok(Ok(3)));
+        assert_eq!(brute::Solver.part2(&model), Ok(Ok(3)));
+        let model = Solver.parse("\
+L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22B, 22B)
+").unwrap();
+        assert_eq!(Solver.part2(&model), Ok(Ok(1)));
+        assert_eq!(brute::Solver.part2(&model), Ok(Ok(1)));
+    }
+
+    // Small networks of any shape, with one round being a single step.
+    #[test]
+    fn random_networks() {
+        let mut rng = Rng::new(8);
+        for _ in 0..300 {
+            let n = 2 + rng.index(8);
+            let names: Vec<String> = (0..n)
+                .map(|i| format!("0{}{}", i, if i < 2 { 'A' } else { rng.choose(&['B', 'Z', 'Z']) }))
+                .collect();
+            let mut input = String::from("L\n\n");
+            for name in &names {
+                let next = &names[rng.index(n)];
+                input.push_str(&format!("{} = ({}, {})\n", name, next, next));
+            }
+            let model = Solver.parse(&input).unwrap();
+            assert_eq!(Solver.part2(&model), brute::Solver.part2(&model), "{}", input);
+        }
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ ghosts: 3, length: 5, rounds: 13 };
+        for seed in 0..20 {
+            let model = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            assert_eq!(Solver.part1(&model).unwrap() % model.rllen(), 0);
+            assert_eq!(Solver.part2(&model), brute::Solver.part2(&model));
+        }
+    }
+}
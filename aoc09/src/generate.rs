@@ -0,0 +1,54 @@
+// Random inputs: histories that are values of polynomials at
+// consecutive points, so that the differences eventually vanish.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("lines", "n", "number of histories (default 200)"),
+    Opt::value("length", "n", "number of values in a history (default 21)"),
+    Opt::value("degree", "n", "maximum degree of the polynomials (default 6)"),
+];
+
+pub struct Generator {
+    pub lines: usize,
+    pub length: usize,
+    pub degree: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ lines: 200, length: 21, degree: 6 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            lines: args.value_in("lines", d.lines, 1..)?,
+            length: args.value_in("length", d.length, 2..=30)?,
+            degree: args.value_in("degree", d.degree, 0..=8)?,
+        })
+    }
+
+    // The degree stays below the length minus one, so that there are
+    // enough values to get down to a row of zeros.
+    fn history(&self, rng: &mut Rng) -> String {
+        let degree = rng.index(self.degree.min(self.length - 2) + 1);
+        let coefficients = (0..=degree).map(|_| rng.range(-10..=10)).collect::<Vec<_>>();
+        let start = rng.range(-5..=5);
+        (start..start + self.length as i64)
+            .map(|x| coefficients.iter().rev().fold(0, |v, c| v * x + c).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.lines).map(|_| self.history(rng) + "\n").collect()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
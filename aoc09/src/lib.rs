@@ -0,0 +1,143 @@
+// Advent-of-Code 2023
+// Day 09
+// Author: Matthias Blume
+
+use std::io::BufRead;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+
+pub mod generate;
+
+pub const DAY: usize = 9;
+
+pub const OPTIONS: &[Opt] = &[];
+
+struct LR(i64, i64);
+
+impl std::iter::Sum for LR {
+    fn sum<I: Iterator<Item = LR>>(iter: I) -> LR {
+        iter.fold(LR(0, 0), |x, y| LR(x.0+y.0, x.1+y.1))
+    }
+}
+
+fn extrapolate(v: Vec<i64>) -> LR {
+    if v.iter().all(|&x| x == 0) {
+        LR(0, 0)
+    } else {
+        let last_i = v.len()-1;
+        let LR(l, r) = extrapolate((0..last_i).map(|i| v[i+1]-v[i]).collect());
+        LR(v[0] - l, v[last_i] + r)
+    }
+}
+
+fn read_history(line: Line) -> Result<Vec<i64>, ParseError> {
+    let history = line.text.split_whitespace()
+        .map(|x| line.parse(x, "number"))
+        .collect::<Result<Vec<_>, _>>()?;
+    if history.is_empty() { return Err(line.fail("empty history")) }
+    Ok(history)
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+        parse::lines(input).map(read_history).collect()
+    }
+
+    fn part1(&self, histories: &Vec<Vec<i64>>) -> i64 {
+        histories.iter().map(|h| extrapolate(h.clone())).sum::<LR>().1
+    }
+
+    fn part2(&self, histories: &Vec<Vec<i64>>) -> i64 {
+        histories.iter().map(|h| extrapolate(h.clone())).sum::<LR>().0
+    }
+}
+
+// Each history counts on its own, so only running sums are kept.
+impl Streaming for Solver {
+    type Record = Vec<i64>;
+    type State1 = i64;
+    type State2 = i64;
+
+    fn record(&self, line: Line) -> Result<Vec<i64>, ParseError> {
+        read_history(line)
+    }
+
+    fn add1(&self, sum: &mut i64, history: &Vec<i64>) {
+        *sum += extrapolate(history.clone()).1
+    }
+
+    fn add2(&self, sum: &mut i64, history: &Vec<i64>) {
+        *sum += extrapolate(history.clone()).0
+    }
+
+    fn answer1(&self, sum: i64) -> i64 {
+        sum
+    }
+
+    fn answer2(&self, sum: i64) -> i64 {
+        sum
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    puzzle::solve_stream(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 114);
+        assert_eq!(Solver.part2(&model), 2);
+    }
+
+    #[test]
+    fn streaming() {
+        assert_eq!(puzzle::stream_answers(&Solver, &mut EXAMPLE.as_bytes()).unwrap(), (114, 2));
+    }
+
+    // Extrapolating backwards is extrapolating the reversed histories.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ lines: 50, ..Default::default() };
+        for seed in 0..20 {
+            let mut model = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let forward = Solver.part1(&model);
+            let backward = Solver.part2(&model);
+            model.iter_mut().for_each(|h| h.reverse());
+            assert_eq!(Solver.part2(&model), forward);
+            assert_eq!(Solver.part1(&model), backward);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Random inputs: a single loop of pipes, with the start tile somewhere
+// on it and unconnected junk pipes all around.
+
+use util::cli::{Args, Opt, UsageError};
+use util::direction::Direction;
+use util::grid::Grid;
+use util::random::Rng;
+
+use Direction::*;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the field (default 140)"),
+    Opt::value("fill", "p", "share of the field that the loop tries to enclose (default 0.5)"),
+    Opt::value("junk", "p", "chance of a pipe on each tile off the loop (default 0.5)"),
+];
+
+const PIPES: &[char] = &['|', '-', 'L', 'J', '7', 'F'];
+
+pub struct Generator {
+    pub size: usize,
+    pub fill: f64,
+    pub junk: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 140, fill: 0.5, junk: 0.5 }
+    }
+}
+
+// The pipe that connects the two sides.
+fn pipe(a: Direction, b: Direction) -> char {
+    match (a.min(b), a.max(b)) {
+        (North, South) => '|',
+        (East, West) => '-',
+        (North, East) => 'L',
+        (North, West) => 'J',
+        (South, West) => '7',
+        (East, South) => 'F',
+        _ => panic!("pipe must connect two different sides"),
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 3..)?,
+            fill: args.value_in("fill", d.fill, 0.0..=1.0)?,
+            junk: args.value_in("junk", d.junk, 0.0..=1.0)?,
+        })
+    }
+
+    // The loop goes around a random blob on a lattice with every other
+    // tile, so that its straight stretches and turns alternate freely
+    // while it never touches itself.  The junk comes last, so that the
+    // loop only depends on the seed.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let n = (self.size - 1) / 2;
+        let squares = ((n * n) as f64 * self.fill).max(1.0) as usize;
+        let ((r, c), steps) = rng.closed_loop(n, n, squares);
+        let moves = steps.iter().flat_map(|&d| [d, d]).collect::<Vec<_>>();
+        let mut grid = Grid::filled(self.size, self.size, '.');
+        let mut p = (2 * r as i64, 2 * c as i64);
+        let mut cells = Vec::new();
+        for (i, &d) in moves.iter().enumerate() {
+            let came = moves[(i + moves.len() - 1) % moves.len()];
+            let pos = (p.0 as usize, p.1 as usize);
+            grid[pos] = pipe(came.reverse(), d);
+            cells.push(pos);
+            p = d.step(p);
+        }
+        grid[rng.choose(&cells)] = 'S';
+        for r in 0..self.size {
+            for c in 0..self.size {
+                if grid[(r, c)] == '.' && rng.chance(self.junk) {
+                    grid[(r, c)] = rng.choose(PIPES);
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,342 @@
+// Advent-of-Code 2023
+// Day 10
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::direction::Direction;
+use util::grid::{Grid, Pos};
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::{self, Color, Picture};
+
+pub mod generate;
+
+pub const DAY: usize = 10;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("render", "file", "draw the loop and the tiles it encloses to the given .svg or .ppm file"),
+];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Tile {
+    Ground,
+    EW,
+    NS,
+    NE,
+    SE,
+    NW,
+    SW,
+}
+
+use crate::Tile::*;
+use Direction::*;
+
+fn read_tile(c: char) -> Option<Option<Tile>> {
+    match c {
+        'S' => Some(None),
+        '.' => Some(Some(Ground)),
+        '-' => Some(Some(EW)),
+        '|' => Some(Some(NS)),
+        'L' => Some(Some(NE)),
+        'J' => Some(Some(NW)),
+        '7' => Some(Some(SW)),
+        'F' => Some(Some(SE)),
+        _ => None,
+    }
+}
+
+// On the StartBoard one of the tiles is None, indicating the
+// start tile ('S').  On the LoopBoard the only non-Ground tiles
+// are those that are in the loop, and the start tile has been
+// filled in with a proper Tile.
+type StartBoard = Grid<Option<Tile>>;
+type LoopBoard = Grid<Tile>;
+
+fn read_board(c: &str) -> Result<StartBoard, ParseError> {
+    Grid::parse(c, read_tile, "tile")
+}
+
+// Find the location of the start tile on a StartBoard.
+fn start_pos(board: &StartBoard) -> Option<Pos> {
+    board.position(|x| x.is_none())
+}
+
+// Make a blank LoopBoard with the same dimensions as the given StartBoard.
+fn blank_board(board: &StartBoard) -> LoopBoard {
+    board.map(|_| Ground)
+}
+
+// Given a tile and one of its directions, get the other direction.
+fn out_direction(in_direction: Direction, t: Tile) -> Option<Direction> {
+    match (in_direction, t) {
+        (East, NE) | (West, NW) | (South, NS) => Some(North),
+        (East, SE) | (West, SW) | (North, NS) => Some(South),
+        (West, EW) | (North, NE) | (South, SE) => Some(East),
+        (East, EW) | (North, NW) | (South, SW) => Some(West),
+        _ => None,
+    }
+}
+
+// Given two directions, return the tile that connects them.
+fn connecting_pipe(d1: Direction, d2: Direction) -> Option<Tile> {
+    match (d1, d2) {
+        (East, West) | (West, East) => Some(EW),
+        (North, South) | (South, North) => Some(NS),
+        (North, East) | (East, North) => Some(NE),
+        (North, West) | (West, North) => Some(NW),
+        (South, East) | (East, South) => Some(SE),
+        (South, West) | (West, South) => Some(SW),
+        _ => None
+    }
+}
+
+type LoopDistanceAndBoard = (usize, LoopBoard);
+
+// Given the position of the start tile and a starting direction, follow the loop
+// if possible back to the start location, calculate the distance to the most
+// distant point along the loop, and produce a LoopBoard containing the loop
+// (with the start tile filled in).
+fn try_start_direction(start_pos: Pos, start_dir: Direction, board: &StartBoard)
+                       -> Option<LoopDistanceAndBoard> {
+    let mut steps = 0;
+    let mut pos = board.go(start_pos, start_dir)?;
+    let mut prev = start_dir.reverse();
+    let mut loop_board = blank_board(board);
+    loop {
+        steps += 1;
+        match board[pos] {
+            Some(t) => {
+                loop_board[pos] = t;
+                let dir = out_direction(prev, t)?;
+                pos = board.go(pos, dir)?;
+                prev = dir.reverse();
+            },
+            None => {
+                loop_board[pos] = connecting_pipe(start_dir, prev)?;
+                return Some((steps / 2, loop_board));
+            },
+        }
+    }
+}
+
+// Find the loop on the given board and return distance to most distant point
+// as well as the filled-in LoopBoard for it.
+fn loop_info(board: &StartBoard) -> Option<LoopDistanceAndBoard> {
+    let start_pos = start_pos(board)?;
+    [North, East, South]
+        .iter()
+        .find_map(|&d| try_start_direction(start_pos, d, board))
+}
+
+// Which tiles of an individual LoopRow are enclosed by the loop.
+fn loop_row_inside(row: &[Tile]) -> impl Iterator<Item = bool> + '_ {
+    row.iter()
+        .scan((false, false), |state, tile| {
+            let (inside, was_north) = *state;
+            // NS always alternates between inside and outside.
+            // Neither NE EW... NW nor SE EW... SW switch inside and outside.
+            // Both NE EW... SW and SE EW... NW do switch (act like NS).
+            // EW does nothing.
+            // Ground is counted as enclosed area when currently inside.
+            let enclosed = *tile == Ground && inside;
+            *state = match tile {
+                Ground | EW => (inside, was_north),
+                NS => (!inside, false),
+                NE => (inside, true),   // L---
+                SE => (inside, false),  // F---
+                NW => (was_north == inside, false),  // L---J  vs. F---J
+                SW => (was_north != inside, false),  // F---7  vs. L---7
+            };
+            Some(enclosed)
+        })
+}
+
+// Calculate area inside loop on an individual LoopRow.
+fn loop_row_area(row: &[Tile]) -> usize {
+    loop_row_inside(row).filter(|&enclosed| enclosed).count()
+}
+
+// Count area inside the loop on a LoopBoard.
+fn loop_board_area(board: &LoopBoard) -> usize {
+    board.rows().map(loop_row_area).sum()
+}
+
+// Positions of the tiles enclosed by the loop.
+fn enclosed(board: &LoopBoard) -> Vec<Pos> {
+    board.rows()
+        .enumerate()
+        .flat_map(|(i, row)| loop_row_inside(row)
+                  .enumerate()
+                  .filter(|&(_, enclosed)| enclosed)
+                  .map(move |(j, _)| (i, j)))
+        .collect()
+}
+
+// The positions along the loop, starting anywhere.
+fn loop_positions(board: &LoopBoard) -> Vec<Pos> {
+    let Some(start) = board.position(|&t| t != Ground) else { return Vec::new() };
+    let mut from = Direction::ALL.into_iter()
+        .find(|&d| out_direction(d, board[start]).is_some())
+        .expect("pipe");
+    let mut pos = start;
+    let mut positions = Vec::new();
+    loop {
+        positions.push(pos);
+        let Some(next) = out_direction(from, board[pos]).and_then(|d| {
+            from = d.reverse();
+            board.go(pos, d)
+        }) else { break };
+        if next == start { break }
+        pos = next;
+    }
+    positions
+}
+
+// The loop, with the tiles it encloses highlighted.
+fn picture(board: &LoopBoard) -> Picture {
+    let mut picture = Picture::from(board, |&t| if t == Ground { Color::WHITE } else { Color::GRAY });
+    picture.highlight(enclosed(board), Color::GREEN);
+    let points = loop_positions(board).into_iter().map(|(r, c)| (r as f64 + 0.5, c as f64 + 0.5));
+    picture.polygon(points, Color::BLUE);
+    picture
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = LoopDistanceAndBoard;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<LoopDistanceAndBoard, ParseError> {
+        let board = read_board(input)?;
+        let (r, c) = start_pos(&board)
+            .ok_or_else(|| parse::eof_error(input, "no start tile"))?;
+        loop_info(&board)
+            .ok_or_else(|| ParseError::new(r + 1, c + 1, "S", "no loop through start tile"))
+    }
+
+    fn part1(&self, (distance, _): &LoopDistanceAndBoard) -> usize {
+        *distance
+    }
+
+    fn part2(&self, (_, loop_board): &LoopDistanceAndBoard) -> usize {
+        loop_board_area(loop_board)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let answers = puzzle::solve(&Solver, args, input)?;
+    if let Some(file) = render::target(args)? {
+        picture(&Solver.parse(input)?.1).write(&file)?;
+    }
+    Ok(answers)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE1: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....
+";
+
+    const EXAMPLE2: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const EXAMPLE3: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const EXAMPLE4: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    #[test]
+    fn example1() {
+        let model = Solver.parse(EXAMPLE1).unwrap();
+        assert_eq!(Solver.part1(&model), 4);
+    }
+
+    #[test]
+    fn example2() {
+        let model = Solver.parse(EXAMPLE2).unwrap();
+        assert_eq!(Solver.part1(&model), 8);
+    }
+
+    #[test]
+    fn example3() {
+        let model = Solver.parse(EXAMPLE3).unwrap();
+        assert_eq!(Solver.part2(&model), 4);
+    }
+
+    #[test]
+    fn example4() {
+        let model = Solver.parse(EXAMPLE4).unwrap();
+        assert_eq!(Solver.part2(&model), 8);
+    }
+
+    // Junk around the loop changes nothing.
+    #[test]
+    fn generated_inputs() {
+        for seed in 0..20 {
+            let clean = generate::Generator{ size: 31, junk: 0.0, ..Default::default() }
+                .generate(&mut Rng::new(seed));
+            let junky = generate::Generator{ size: 31, junk: 1.0, ..Default::default() }
+                .generate(&mut Rng::new(seed));
+            let clean = Solver.parse(&clean).unwrap();
+            let junky = Solver.parse(&junky).unwrap();
+            assert_eq!(Solver.part1(&clean), Solver.part1(&junky));
+            assert_eq!(Solver.part2(&clean), Solver.part2(&junky));
+            let pipes = clean.1.iter().filter(|&(_, &t)| t != Ground).count();
+            assert_eq!(Solver.part1(&clean), pipes / 2);
+        }
+    }
+
+    #[test]
+    fn loop_and_enclosed_tiles() {
+        let (_, board) = Solver.parse(EXAMPLE4).unwrap();
+        let positions = loop_positions(&board);
+        assert_eq!(positions.len(), board.iter().filter(|&(_, &t)| t != Ground).count());
+        assert!(positions.windows(2).all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+        let enclosed = enclosed(&board);
+        assert_eq!(enclosed.len(), 8);
+        assert!(enclosed.iter().all(|&p| board[p] == Ground && !positions.contains(&p)));
+    }
+}
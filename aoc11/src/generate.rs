@@ -0,0 +1,53 @@
+// Random inputs: an image of space with galaxies scattered over it and
+// some rows and columns left empty.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the image (default 140)"),
+    Opt::value("galaxies", "p", "chance of a galaxy at each point (default 0.02)"),
+    Opt::value("empty", "p", "chance of a row or column being kept empty (default 0.05)"),
+];
+
+pub struct Generator {
+    pub size: usize,
+    pub galaxies: f64,
+    pub empty: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 140, galaxies: 0.02, empty: 0.05 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 1..)?,
+            galaxies: args.value_in("galaxies", d.galaxies, 0.0..=1.0)?,
+            empty: args.value_in("empty", d.empty, 0.0..=1.0)?,
+        })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let rows = (0..self.size).map(|_| !rng.chance(self.empty)).collect::<Vec<_>>();
+        let columns = (0..self.size).map(|_| !rng.chance(self.empty)).collect::<Vec<_>>();
+        let mut grid = Grid::filled(self.size, self.size, '.');
+        for r in 0..self.size {
+            for c in 0..self.size {
+                if rows[r] && columns[c] && rng.chance(self.galaxies) {
+                    grid[(r, c)] = '#';
+                }
+            }
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
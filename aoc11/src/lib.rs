@@ -0,0 +1,151 @@
+// Advent-of-Code 2023
+// Day 11
+// Author: Matthias Blume
+
+use std::collections::HashSet;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult, UsageError};
+use util::grid::{Grid, Pos};
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+
+pub mod generate;
+
+pub const DAY: usize = 11;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("stretch", "factor", "expansion factor of empty space (part 2, default 1000000)"),
+];
+
+type HalfTransform = Vec<usize>;
+type CoordTransform = (HalfTransform, HalfTransform);
+
+fn read_galaxies(contents: &str) -> Result<Vec<Pos>, ParseError> {
+    let space = Grid::parse(contents, |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    }, "space")?;
+    Ok(space.iter().filter_map(|(pos, &galaxy)| galaxy.then_some(pos)).collect())
+}
+
+fn half_transform<'a>(
+    factor: usize, galaxies: &'a [Pos], select: impl Fn(&'a Pos) -> usize)
+    -> HalfTransform {
+    let occupied: HashSet<usize> = galaxies.iter().map(select).collect();
+    let largest = occupied.iter().max().copied().unwrap_or(0);
+    (0..=largest).scan(0, |stretch, i| {
+        let s = *stretch;
+        if !occupied.contains(&i) { *stretch += factor-1 }
+        Some(s + i)
+    }).collect()
+}
+
+fn transform_pos((r, c): Pos, (rt, ct): &CoordTransform) -> Pos {
+    (rt[r], ct[c])
+}
+
+fn total_distance(galaxies: &[Pos], stretch_factor: usize) -> usize {
+    let trans = (half_transform(stretch_factor, galaxies, |&(r, _)| r),
+                 half_transform(stretch_factor, galaxies, |&(_, c)| c));
+    let len = galaxies.len();
+    (0..len)
+        .fold(0, |isum, i| {
+            let pi = transform_pos(galaxies[i], &trans);
+            (i+1..len).fold(isum, |jsum, j| {
+                let pj = transform_pos(galaxies[j], &trans);
+                jsum + pi.0.abs_diff(pj.0) + pi.1.abs_diff(pj.1)
+            })
+        })
+}
+
+pub struct Solver {
+    stretch_factor: usize,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        Ok(Solver{ stretch_factor: args.value_or("stretch", 1000000)? })
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Vec<Pos>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Pos>, ParseError> {
+        read_galaxies(input)
+    }
+
+    fn part1(&self, galaxies: &Vec<Pos>) -> usize {
+        total_distance(galaxies, 2)
+    }
+
+    fn part2(&self, galaxies: &Vec<Pos>) -> usize {
+        total_distance(galaxies, self.stretch_factor)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver{ stretch_factor: 10 };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), 374);
+        assert_eq!(solver.part2(&model), 1030);
+    }
+
+    #[test]
+    fn example_stretch_100() {
+        let solver = Solver{ stretch_factor: 100 };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part2(&model), 8410);
+    }
+
+    // Without stretching, distances are plain, and every extra bit of
+    // stretch adds the same amount.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 40, galaxies: 0.05, empty: 0.2 };
+        for seed in 0..20 {
+            let galaxies = Solver{ stretch_factor: 1 }.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let plain = galaxies.iter()
+                .flat_map(|a| galaxies.iter().map(move |b| a.0.abs_diff(b.0) + a.1.abs_diff(b.1)))
+                .sum::<usize>() / 2;
+            let distance = |f| total_distance(&galaxies, f);
+            assert_eq!(distance(1), plain);
+            assert_eq!(distance(3) - distance(2), distance(2) - distance(1));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+// Random inputs: condition records made from an actual row of springs
+// by hiding some of them, so that there is at least one arrangement.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("records", "n", "number of records (default 1000)"),
+    Opt::value("length", "n", "maximum number of springs in a record (default 20)"),
+    Opt::value("damaged", "p", "chance of each spring being damaged (default 0.5)"),
+    Opt::value("unknown", "p", "chance of each spring being hidden (default 0.5)"),
+];
+
+pub struct Generator {
+    pub records: usize,
+    pub length: usize,
+    pub damaged: f64,
+    pub unknown: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ records: 1000, length: 20, damaged: 0.5, unknown: 0.5 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            records: args.value_in("records", d.records, 1..)?,
+            length: args.value_in("length", d.length, 1..)?,
+            damaged: args.value_in("damaged", d.damaged, 0.0..=1.0)?,
+            unknown: args.value_in("unknown", d.unknown, 0.0..=1.0)?,
+        })
+    }
+
+    // Every record has at least one damaged spring.
+    fn record(&self, rng: &mut Rng) -> String {
+        let len = 1 + rng.index(self.length);
+        let mut springs = (0..len)
+            .map(|_| if rng.chance(self.damaged) { '#' } else { '.' })
+            .collect::<Vec<_>>();
+        if !springs.contains(&'#') {
+            springs[rng.index(len)] = '#';
+        }
+        let groups = springs.split(|&c| c == '.')
+            .filter(|g| !g.is_empty())
+            .map(|g| g.len().to_string())
+            .collect::<Vec<_>>();
+        let hidden = springs.iter()
+            .map(|&c| if rng.chance(self.unknown) { '?' } else { c })
+            .collect::<String>();
+        format!("{} {}", hidden, groups.join(","))
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.records).map(|_| self.record(rng) + "\n").collect()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
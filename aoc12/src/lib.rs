@@ -0,0 +1,312 @@
+// Advent-of-Code 2023
+// Day 12
+// Author: Matthias Blume
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+use util::bench::{self, BenchResult};
+use util::bigint::{BigUint, BIG};
+use util::cli::{Args, Opt, RunResult, UsageError};
+use util::parse::{self, Line, ParseError};
+use util::math::{self, Count, Overflow, Total};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming, ToAnswer};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 12;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("unfold", "factor", "unfold factor, at least 1 (part 2, default 5)"),
+    BIG,
+];
+
+// Splits input line into the map part (a String) and the vector of
+// group lengths.
+fn read_line(line: Line) -> Result<(String, Vec<u64>), ParseError> {
+    match line.text.split_whitespace().boxed()[..] {
+        [s, nums] => {
+            if let Some((i, c)) = s.char_indices().find(|&(_, c)| !"#.?".contains(c)) {
+                return Err(line.error(&s[i..i + c.len_utf8()], "bad spring"))
+            }
+            Ok((s.to_string(),
+                nums.split(',')
+                .map(|x| line.parse(x, "group length"))
+                .collect::<Result<_, _>>()?))
+        },
+        _ => Err(line.fail("bad input line")),
+    }
+}
+
+// Unfolds the input map string and the group vector "factor" times.
+fn unfold(s: String, v: Vec<u64>, factor: u32) -> (String, Vec<u64>) {
+    let mut ss = s.to_owned();
+    let mut vv = v.to_owned();
+    for _ in 0 .. factor - 1 {
+        ss.push('?');
+        ss.push_str(&s);
+        vv.extend(&v);
+    }
+    (ss, vv)
+}
+
+// Checks that s does not contain any '#'.
+fn can_be_empty(s: &[u8]) -> bool {
+    s.iter().all(|&c| c != b'#')
+}
+
+// Can a group with the given length fit onto the beginning of s?
+fn can_fit_group(s: &[u8], len: usize) -> bool {
+    s[..len].iter().all(|&c| c != b'.')  // don't sit on '.'
+        && s.get(len) != Some(&b'#')      // don't abut '#' on right
+}
+
+type Memo<N> = HashMap<(usize, usize), N>;
+
+// Counts in how many ways the groups in v can fit onto s, using the
+// memoized version for recursive calls.
+fn cnt<N: Count>(s: &[u8], v: &[u64], memo: &mut Memo<N>) -> Result<N, Overflow> {
+    match v {
+        [] => { // No more groups.  Make sure that tail can be empty.
+            Ok(N::from_u64(if can_be_empty(s) { 1 } else { 0 }))
+        },
+        [vfirst, vrest @ ..] => {
+            let g = *vfirst as usize;
+            // Minimum space needed for groups in v, including gaps.
+            let remaining = v.iter().try_fold(vrest.len() as u64, |n, &g| n.checked_add(g));
+            // Max start pos for first group, if the groups fit at all.
+            let maxpos = remaining.and_then(|r| s.len().checked_sub(usize::try_from(r).ok()?));
+            let Some(maxpos) = maxpos else { return Ok(N::from_u64(0)) };
+            math::sum((0..=maxpos)
+                .map(|p|
+                     if can_be_empty(&s[..p]) && can_fit_group(&s[p..], g) {
+                         let nextpos = s.len().min(p + g + 1);
+                         cnt_memo(&s[nextpos..], vrest, memo)
+                     } else { Ok(N::from_u64(0)) }))
+        }
+    }
+}
+
+// Memoized cnt().
+fn cnt_memo<N: Count>(s: &[u8], v: &[u64], memo: &mut Memo<N>) -> Result<N, Overflow> {
+    let key = (v.len(), s.len());
+    if let Some(n) = memo.get(&key) { Ok(n.clone()) }
+    else {
+        let n = cnt(s, v, memo)?;
+        memo.insert(key, n.clone());
+        Ok(n)
+    }
+}
+
+// Counts in how many ways the groups in v can fit onto s.
+fn count<N: Count>(s: &[u8], v: &[u64]) -> Result<N, Overflow> {
+    cnt_memo(s, v, &mut HashMap::new())
+}
+
+// Unfold and count a single record.
+fn count_record<N: Count>((s, v): &(String, Vec<u64>), factor: u32) -> Result<N, Overflow> {
+    let (s, v) = unfold(s.clone(), v.clone(), factor);
+    count(s.as_bytes(), &v)
+}
+
+// Counts in N, which is u64 unless asked for big integers.  Unfolding
+// more than the usual five times quickly needs them.
+pub struct Solver<N> {
+    factor: u32,
+    count: PhantomData<N>,
+}
+
+impl<N> Solver<N> {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        Ok(Solver{ factor: args.value_in("unfold", 5, 1..)?, count: PhantomData })
+    }
+}
+
+impl<N: Count + ToAnswer> Puzzle for Solver<N> {
+    type Model = Vec<(String, Vec<u64>)>;
+    type Answer1 = Result<N, Overflow>;
+    type Answer2 = Result<N, Overflow>;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse::lines(input).map(read_line).collect()
+    }
+
+    fn part1(&self, records: &Self::Model) -> Result<N, Overflow> {
+        math::sum(records.iter().map(|r| count_record(r, 1)))
+    }
+
+    fn part2(&self, records: &Self::Model) -> Result<N, Overflow> {
+        math::sum(records.iter().map(|r| count_record(r, self.factor)))
+    }
+}
+
+// Each record counts on its own, so only running sums are kept.
+impl<N: Count + ToAnswer> Streaming for Solver<N> {
+    type Record = (String, Vec<u64>);
+    type State1 = Total<N>;
+    type State2 = Total<N>;
+
+    fn record(&self, line: Line) -> Result<Self::Record, ParseError> {
+        read_line(line)
+    }
+
+    fn add1(&self, sum: &mut Total<N>, record: &Self::Record) {
+        sum.add(count_record(record, 1))
+    }
+
+    fn add2(&self, sum: &mut Total<N>, record: &Self::Record) {
+        sum.add(count_record(record, self.factor))
+    }
+
+    fn answer1(&self, sum: Total<N>) -> Result<N, Overflow> {
+        sum.get()
+    }
+
+    fn answer2(&self, sum: Total<N>) -> Result<N, Overflow> {
+        sum.get()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    if args.flag(BIG.name) {
+        puzzle::solve(&Solver::<BigUint>::from(args)?, args, input)
+    } else {
+        puzzle::solve(&Solver::<u64>::from(args)?, args, input)
+    }
+}
+
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    if args.flag(BIG.name) {
+        puzzle::solve_stream(&Solver::<BigUint>::from(args)?, args, input)
+    } else {
+        puzzle::solve_stream(&Solver::<u64>::from(args)?, args, input)
+    }
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    if args.flag(BIG.name) {
+        bench::measure(&Solver::<BigUint>::from(args)?, args, input, runs)
+    } else {
+        bench::measure(&Solver::<u64>::from(args)?, args, input, runs)
+    }
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver::<u64>{ factor: 5, count: PhantomData };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), Ok(21));
+        assert_eq!(solver.part2(&model), Ok(525152));
+    }
+
+    // Groups of 1 in n unknown springs can be placed in C(n - k + 1, k)
+    // ways, which for 20 springs and 5 groups unfolded is C(80, 25).
+    #[test]
+    fn counts_overflow() {
+        let record = format!("{} 1,1,1,1,1\n", "?".repeat(20));
+        let model = Solver::<u64>{ factor: 5, count: PhantomData }.parse(&record).unwrap();
+        assert_eq!(Solver::<u64>{ factor: 5, count: PhantomData }.part2(&model), Err(Overflow));
+        let big = Solver::<BigUint>{ factor: 5, count: PhantomData }.part2(&model).unwrap();
+        assert_eq!(big.to_string(), "363413731121503794368");
+    }
+
+    #[test]
+    fn streaming() {
+        let solver = Solver::<u64>{ factor: 5, count: PhantomData };
+        assert_eq!(puzzle::stream_answers(&solver, &mut EXAMPLE.as_bytes()).unwrap(), (Ok(21), Ok(525152)));
+    }
+
+    fn arrangements(s: &str, v: &[u64]) -> u64 {
+        cnt(s.as_bytes(), v, &mut HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn cnt_example_records() {
+        assert_eq!(arrangements("???.###", &[1, 1, 3]), 1);
+        assert_eq!(arrangements(".??..??...?##.", &[1, 1, 3]), 4);
+        assert_eq!(arrangements("?#?#?#?#?#?#?#?", &[1, 3, 1, 6]), 1);
+        assert_eq!(arrangements("????.#...#...", &[4, 1, 1]), 1);
+        assert_eq!(arrangements("????.######..#####.", &[1, 6, 5]), 4);
+        assert_eq!(arrangements("?###????????", &[3, 2, 1]), 10);
+    }
+
+    #[test]
+    fn cnt_groups_too_long() {
+        assert_eq!(arrangements("??", &[3]), 0);
+        assert_eq!(arrangements("???", &[1, 2]), 0);
+        assert_eq!(arrangements("???", &[1, u64::MAX]), 0);
+    }
+
+    #[test]
+    fn cnt_without_groups() {
+        assert_eq!(arrangements("", &[]), 1);
+        assert_eq!(arrangements("..??.", &[]), 1);
+        assert_eq!(arrangements("..#", &[]), 0);
+    }
+
+    #[test]
+    fn unfold_option() {
+        let solver = |arg: &str| Solver::<u64>::from(&Args::parse([arg.to_string()], OPTIONS).unwrap());
+        assert_eq!(solver("--unfold=1").unwrap().factor, 1);
+        assert!(solver("--unfold=0").is_err());
+    }
+
+    #[test]
+    fn bad_spring() {
+        let line = parse::lines("??é# 1\n").next().unwrap();
+        assert_eq!(read_line(line).unwrap_err().to_string(), "1:3: bad spring: 'é'");
+    }
+
+    // Tries every way of filling in the unknown springs.
+    fn brute_force(s: &str, v: &[u64]) -> u64 {
+        let unknown = s.match_indices('?').map(|(i, _)| i).collect::<Vec<_>>();
+        (0..1u32 << unknown.len())
+            .filter(|bits| {
+                let mut springs = s.as_bytes().to_vec();
+                for (k, &i) in unknown.iter().enumerate() {
+                    springs[i] = if bits & (1 << k) != 0 { b'#' } else { b'.' };
+                }
+                let groups = springs.split(|&c| c == b'.')
+                    .filter(|g| !g.is_empty())
+                    .map(|g| g.len() as u64)
+                    .collect::<Vec<_>>();
+                groups == v
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ records: 50, length: 12, ..Default::default() };
+        let solver = Solver::<u64>{ factor: 5, count: PhantomData };
+        for seed in 0..20 {
+            let model = solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            for (s, v) in &model {
+                let n = arrangements(s, v);
+                assert!(n >= 1, "{} {:?}", s, v);
+                assert_eq!(n, brute_force(s, v), "{} {:?}", s, v);
+            }
+            assert!(solver.part2(&model).unwrap() >= solver.part1(&model).unwrap());
+        }
+    }
+}
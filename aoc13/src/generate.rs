@@ -0,0 +1,80 @@
+// Random inputs: patterns of ash and rocks, each with a planted line of
+// reflection that has a given number of smudges.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("patterns", "n", "number of patterns (default 100)"),
+    Opt::value("size", "n", "maximum height and width of a pattern (default 17)"),
+    Opt::value("smudges", "n", "number of smudges on each planted mirror (default 1)"),
+];
+
+pub struct Generator {
+    pub patterns: usize,
+    pub size: usize,
+    pub smudges: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ patterns: 100, size: 17, smudges: 1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let size = args.value_in("size", d.size, 2..)?;
+        Ok(Generator{
+            patterns: args.value_in("patterns", d.patterns, 1..)?,
+            size,
+            smudges: args.value_in("smudges", d.smudges, 0..=size / 2 * size)?,
+        })
+    }
+
+    // The mirror is planted between rows; transposing half of the
+    // patterns makes for vertical ones.  Smudges are on the near side
+    // of the part that gets reflected, so each accounts for one defect.
+    // Patterns where that part is too small for them are dropped.
+    fn pattern(&self, rng: &mut Rng) -> Grid<char> {
+        loop {
+            if let Some(p) = self.try_pattern(rng) { return p }
+        }
+    }
+
+    fn try_pattern(&self, rng: &mut Rng) -> Option<Grid<char>> {
+        let height = 2 + rng.index(self.size - 1);
+        let width = 1 + rng.index(self.size);
+        let mut p = Grid::filled(height, width, '.');
+        for pos in p.positions() {
+            if rng.chance(0.5) { p[pos] = '#' }
+        }
+        let m = 1 + rng.index(height - 1);
+        let start = (2 * m).max(height) - height;
+        for r in start..m {
+            for c in 0..width {
+                p[(2 * m - r - 1, c)] = p[(r, c)];
+            }
+        }
+        let mut near = (start..m).flat_map(|r| (0..width).map(move |c| (r, c))).collect::<Vec<_>>();
+        if near.len() < self.smudges { return None }
+        rng.shuffle(&mut near);
+        for &pos in near.iter().take(self.smudges) {
+            p[pos] = if p[pos] == '#' { '.' } else { '#' };
+        }
+        Some(if rng.chance(0.5) { p.transposed() } else { p })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        (0..self.patterns)
+            .map(|_| self.pattern(rng).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,191 @@
+// Advent-of-Code 2023
+// Day 13
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult, UsageError};
+use util::direction::Axis;
+use util::grid::Grid;
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+
+pub mod generate;
+
+pub const DAY: usize = 13;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("smudges", "n", "number of smudges on each mirror (part 2, default 1)"),
+];
+
+type Pattern = Grid<bool>;
+type Coords = (usize, usize);
+
+// Mirror lines are either horizontal (between rows) or vertical (between
+// columns).
+use Axis::*;
+
+// Reads pattern dimensions.
+fn dims(p: &Pattern, d: Axis) -> Coords {
+    let (r, c) = (p.height(), p.width());
+    match d { Horizontal => (r, c), Vertical => (c, r) }
+}
+
+// Access the given coordinates in a pattern, considering the direction.
+fn access(p: &Pattern, (i, j): Coords, d: Axis) -> bool {
+    match d { Horizontal => p[(i, j)], Vertical => p[(j, i)] }
+}
+
+// Calculates the number of defects ("smudges") that account for
+// a mirroring line at m (= row or column, depending on direction).
+fn mirror_defects(p: &Pattern, m: usize, d: Axis) -> usize {
+    let (xsz, ysz) = dims(p, d);
+    let m2 = 2 * m;
+    let start = m2.max(xsz) - xsz;
+    (start..m)
+        .map(|i|
+             (0..ysz)
+             .filter(|&j| access(p, (i, j), d) != access(p, (m2 - i - 1, j), d))
+             .count())
+        .sum()
+}
+
+// Scores a mirror line at m.  The score is m if the actual number of smudges
+// matches the expected number.  Otherwise the score is 0.
+fn mirror_score(p: &Pattern, m: usize, defects: usize, d: Axis) -> usize {
+    if mirror_defects(p, m, d) == defects { m } else { 0 }
+}
+
+// Sum of scores of all possible reflection lines, given the expected number
+// of defects (smudges).
+fn reflection_score(p: &Pattern, defects: usize) -> usize {
+    let (nrows, ncols) = dims(p, Horizontal);
+    100 * (1..nrows).map(|i| mirror_score(p, i, defects, Horizontal)).sum::<usize>()
+        + (1..ncols).map(|j| mirror_score(p, j, defects, Vertical)).sum::<usize>()
+}
+
+// Reads a spot.  '#' is true, '.' is false.
+fn read_spot(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+// Patterns are separated by empty lines.
+fn read_patterns(contents: &str) -> Result<Vec<Pattern>, ParseError> {
+    let mut patterns = Vec::new();
+    let mut lines = parse::lines(contents).peekable();
+    loop {
+        let pattern_lines = std::iter::from_fn(|| lines.next_if(|line| !line.text.is_empty()));
+        patterns.push(Grid::from_lines(pattern_lines, read_spot, "spot")?);
+        if lines.next().is_none() { break }
+    }
+    Ok(patterns)
+}
+
+fn total_score(patterns: &[Pattern], defects: usize) -> usize {
+    patterns.iter().map(|p| reflection_score(p, defects)).sum()
+}
+
+pub struct Solver {
+    defects: usize,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        Ok(Solver{ defects: args.value_or("smudges", 1)? })
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Vec<Pattern>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Pattern>, ParseError> {
+        read_patterns(input)
+    }
+
+    fn part1(&self, patterns: &Vec<Pattern>) -> usize {
+        total_score(patterns, 0)
+    }
+
+    fn part2(&self, patterns: &Vec<Pattern>) -> usize {
+        total_score(patterns, self.defects)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver{ defects: 1 };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), 405);
+        assert_eq!(solver.part2(&model), 400);
+    }
+
+    #[test]
+    fn mirror_defects_example() {
+        let patterns = read_patterns(EXAMPLE).unwrap();
+        // The mirror lines without smudges ...
+        assert_eq!(mirror_defects(&patterns[0], 5, Vertical), 0);
+        assert_eq!(mirror_defects(&patterns[1], 4, Horizontal), 0);
+        // ... and with exactly one.
+        assert_eq!(mirror_defects(&patterns[0], 3, Horizontal), 1);
+        assert_eq!(mirror_defects(&patterns[1], 1, Horizontal), 1);
+        // Not a mirror line at all.
+        assert!(mirror_defects(&patterns[0], 1, Vertical) > 1);
+    }
+
+    // Every pattern has a mirror with the planted number of smudges.
+    #[test]
+    fn generated_inputs() {
+        for smudges in [0, 1, 2] {
+            let gen = generate::Generator{ patterns: 50, size: 12, smudges };
+            for seed in 0..10 {
+                let patterns = Solver{ defects: smudges }.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+                assert_eq!(patterns.len(), 50);
+                for p in &patterns {
+                    let mirrors = (1..p.height()).any(|m| mirror_defects(p, m, Horizontal) == smudges)
+                        || (1..p.width()).any(|m| mirror_defects(p, m, Vertical) == smudges);
+                    assert!(mirrors);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Random inputs: a platform with round and cube-shaped rocks.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the platform (default 100)"),
+    Opt::value("round", "p", "chance of a round rock at each point (default 0.2)"),
+    Opt::value("cubes", "p", "chance of a cube-shaped rock at each point (default 0.1)"),
+];
+
+pub struct Generator {
+    pub size: usize,
+    pub round: f64,
+    pub cubes: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 100, round: 0.2, cubes: 0.1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 1..)?,
+            round: args.value_in("round", d.round, 0.0..=1.0)?,
+            cubes: args.value_in("cubes", d.cubes, 0.0..=1.0)?,
+        })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut grid = Grid::filled(self.size, self.size, '.');
+        for pos in grid.positions() {
+            if rng.chance(self.round) {
+                grid[pos] = 'O';
+            } else if rng.chance(self.cubes) {
+                grid[pos] = '#';
+            }
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,266 @@
+// Advent-of-Code 2023
+// Day 14
+// Author: Matthias Blume
+
+use util::animate::{self, Frame, Simulation};
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::direction::{Axis, Direction};
+use util::cycle;
+use util::grid::{Grid, Pos};
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::Color;
+
+pub mod generate;
+
+pub const DAY: usize = 14;
+
+pub const OPTIONS: &[Opt] = &[animate::ANIMATE, animate::FPS, animate::FRAMES];
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum Item {
+    Nothing,
+    Square,
+    Round,
+}
+use crate::Item::*;
+
+use Axis::*;
+use Direction::*;
+
+struct Board {
+    items: Grid<Item>,
+}
+
+impl Board {
+    fn new(items: Grid<Item>) -> Board {
+        Board { items }
+    }
+
+    // Item at index i along the given axis and index j across it.
+    fn at(&mut self, i: usize, j: usize, axis: Axis) -> &mut Item {
+        match axis {
+            Vertical => &mut self.items[(i, j)],
+            Horizontal => &mut self.items[(j, i)],
+        }
+    }
+
+    // Round rocks roll in the given direction.
+    fn tilt(mut self, direction: Direction) -> Self {
+        let axis = direction.axis();
+        let iend = match axis {
+            Vertical => self.items.height(),
+            Horizontal => self.items.width(),
+        };
+        let jend = match axis {
+            Vertical => self.items.width(),
+            Horizontal => self.items.height(),
+        };
+        let (istart, istop, increment) = match direction {
+            North | West => (0, iend as i32 - 1, 1),
+            South | East => (iend as i32 - 1, 0, -1),
+        };
+        for j in 0..jend {
+            let mut free = istart;
+            for i in num::range_step_inclusive(istart, istop, increment) {
+                match self.at(i as usize, j, axis) {
+                    Nothing => (),
+                    Square => free = i + increment,
+                    Round => {
+                        let new_i = free;
+                        free = new_i + increment;
+                        *self.at(i as usize, j, axis) = Nothing;
+                        *self.at(new_i as usize, j, axis) = Round;
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    fn cycle(self) -> Self {
+        self.tilt(North)
+            .tilt(West)
+            .tilt(South)
+            .tilt(East)
+    }
+
+    fn weight(&self) -> usize {
+        self.items
+            .rows()
+            .enumerate()
+            .map(|(r, row)| (self.items.height() - r) * row.iter().filter(|&item| item == &Round).count())
+            .sum()
+    }
+
+    // Positions of the round rocks, which is all that changes.
+    fn summarize(&self) -> Vec<Pos> {
+        self.items.iter()
+            .filter_map(|(pos, &item)| (item == Round).then_some(pos))
+            .collect()
+    }
+
+    fn ncycle(self, n: u64) -> Self {
+        cycle::with_history(self, n, Board::cycle, Board::summarize).state
+    }
+}
+
+// Tilting shown one step at a time: in each step, every round rock that
+// can still roll moves by one place.  Part 1 only tilts north, part 2
+// spins cycles.
+struct Tilting {
+    board: Board,
+    directions: &'static [Direction],
+    tilts: usize,
+    idle: usize,  // tilts in a row that moved nothing
+}
+
+impl Tilting {
+    fn new(items: Grid<Item>, part: Option<Part>) -> Self {
+        let directions: &[Direction] = if part == Some(Part::One) { &[North] } else { &[North, West, South, East] };
+        Tilting{ board: Board::new(items), directions, tilts: 0, idle: 0 }
+    }
+
+    fn direction(&self) -> Direction {
+        self.directions[self.tilts % self.directions.len()]
+    }
+
+    // Rolls the rocks one place, front ones first so that rows of
+    // rocks move together.  Tells whether any did.
+    fn roll(&mut self, d: Direction) -> bool {
+        let (dr, dc) = d.delta();
+        let mut rocks = self.board.summarize();
+        rocks.sort_by_key(|&(r, c)| -(r as i64 * dr + c as i64 * dc));
+        let mut moved = false;
+        for p in rocks {
+            if let Some(q) = self.board.items.go(p, d).filter(|&q| self.board.items[q] == Nothing) {
+                self.board.items[p] = Nothing;
+                self.board.items[q] = Round;
+                moved = true;
+            }
+        }
+        moved
+    }
+}
+
+impl Simulation for Tilting {
+    fn frame(&self) -> Frame {
+        let caption = format!("cycle {}, tilting {:?}, load {}",
+                              self.tilts / 4 + 1, self.direction(), self.board.weight());
+        Frame::from(&self.board.items, |item| match item {
+            Nothing => ('.', Color(90, 90, 90)),
+            Square => ('#', Color::GRAY),
+            Round => ('O', Color::YELLOW),
+        }, caption)
+    }
+
+    // Moves on to the next tilt once the rocks stop, and stops when no
+    // tilt moves them any more.
+    fn step(&mut self) -> bool {
+        if self.roll(self.direction()) {
+            self.idle = 0;
+            return true
+        }
+        self.tilts += 1;
+        self.idle += 1;
+        self.idle < self.directions.len()
+    }
+}
+
+fn read_item(c: char) -> Option<Item> {
+    match c {
+        '.' => Some(Nothing),
+        '#' => Some(Square),
+        'O' => Some(Round),
+        _ => None,
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Grid<Item>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Grid<Item>, ParseError> {
+        Grid::parse(input, read_item, "item")
+    }
+
+    fn part1(&self, items: &Grid<Item>) -> usize {
+        Board::new(items.clone()).tilt(North).weight()
+    }
+
+    fn part2(&self, items: &Grid<Item>) -> usize {
+        Board::new(items.clone()).ncycle(1000000000).weight()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    animate::play(args, || Ok(Tilting::new(Solver.parse(input)?, args.part)))?;
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 136);
+        assert_eq!(Solver.part2(&model), 64);
+    }
+
+    // Skipping ahead by way of the cycle gets to the same place as
+    // spinning one cycle at a time.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 12, ..Default::default() };
+        for seed in 0..10 {
+            let items = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let mut board = Board::new(items.clone());
+            for n in 0..40 {
+                assert_eq!(Board::new(items.clone()).ncycle(n).summarize(), board.summarize());
+                board = board.cycle();
+            }
+        }
+    }
+
+    // Rolling one place at a time ends up where tilting all the way does.
+    #[test]
+    fn tilting_step_by_step() {
+        let items = Solver.parse(EXAMPLE).unwrap();
+        let mut tilting = Tilting::new(items.clone(), None);
+        while tilting.tilts < 4 {
+            assert!(tilting.step());
+        }
+        assert_eq!(tilting.board.summarize(), Board::new(items.clone()).cycle().summarize());
+        let mut tilting = Tilting::new(items.clone(), Some(Part::One));
+        while tilting.step() {}
+        assert_eq!(tilting.board.weight(), 136);
+    }
+}
@@ -0,0 +1,61 @@
+// Random inputs: an initialization sequence of steps on labels drawn
+// from a fixed pool, so that lenses get replaced and removed.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("steps", "n", "number of steps (default 4000)"),
+    Opt::value("labels", "n", "number of distinct labels (default 500)"),
+    Opt::value("removals", "p", "chance of each step removing a lens (default 0.3)"),
+];
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator {
+    pub steps: usize,
+    pub labels: usize,
+    pub removals: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ steps: 4000, labels: 500, removals: 0.3 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            steps: args.value_in("steps", d.steps, 1..)?,
+            labels: args.value_in("labels", d.labels, 1..)?,
+            removals: args.value_in("removals", d.removals, 0.0..=1.0)?,
+        })
+    }
+
+    // The pool may have duplicates, which does no harm.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let labels = (0..self.labels)
+            .map(|_| {
+                let len = 1 + rng.index(6);
+                rng.word(len, LETTERS)
+            })
+            .collect::<Vec<_>>();
+        let steps = (0..self.steps)
+            .map(|_| {
+                let label = &labels[rng.index(labels.len())];
+                if rng.chance(self.removals) {
+                    format!("{}-", label)
+                } else {
+                    format!("{}={}", label, rng.range(1..=9))
+                }
+            })
+            .collect::<Vec<_>>();
+        steps.join(",") + "\n"
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
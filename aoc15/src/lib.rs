@@ -0,0 +1,231 @@
+// Advent-of-Code 2023
+// Day 15
+// Author: Matthias Blume
+
+use std::io::BufRead;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult, Streaming};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 15;
+
+pub const OPTIONS: &[Opt] = &[];
+
+mod hash {
+    pub fn ascii(s: &str) -> usize {
+        s.as_bytes().iter().fold(0, |accu, &c| ((accu + c as usize) * 17) % 256)
+    }
+}
+
+struct Lens {
+    label: String,
+    strength: usize,
+}
+
+pub enum Instruction {
+    AddLens{ label: String, strength: usize, hash: usize },
+    RemoveLens{ label: String, hash: usize },
+}
+use crate::Instruction::*;
+
+impl Instruction {
+    fn from(line: &Line, input: &str) -> Result<Self, ParseError> {
+        match input.split('=').boxed()[..] {
+            [label, s] => {
+                let strength = line.parse(s, "lens strength")?;
+                Ok(AddLens{ label: label.to_string(), strength, hash: hash::ascii(label) })
+            },
+            _ => {
+                let label = input.strip_suffix('-')
+                    .ok_or_else(|| line.error(input, "bad instruction"))?;
+                Ok(RemoveLens{ label: label.to_string(), hash: hash::ascii(label) })
+            }
+        }
+    }
+}
+
+// An initialization step as written (for part 1) and as understood (for part 2).
+pub struct Step(String, Instruction);
+
+fn read_step(line: &Line, s: &str) -> Result<Step, ParseError> {
+    Ok(Step(s.to_string(), Instruction::from(line, s)?))
+}
+
+fn read_steps(line: Line) -> Result<Vec<Step>, ParseError> {
+    line.text.split(',').map(|s| read_step(&line, s)).collect()
+}
+
+struct Box {
+    n: usize,  // box number
+    lenses: Vec<Lens>,
+}
+
+impl Box {
+    fn lens_position(&self, label: &str) -> Option<usize> {
+        self.lenses.iter().position(|lens| lens.label == label)
+    }
+
+    fn remove_lens(&mut self, label: &str) {
+        if let Some(i) = self.lens_position(label) {
+            self.lenses.remove(i);
+        }
+    }
+
+    fn insert_lens(&mut self, label: &str, strength: usize) {
+        let lens = Lens{ label: label.to_string(), strength };
+        if let Some(i) = self.lens_position(label) {
+            self.lenses[i] = lens;
+        } else {
+            self.lenses.push(lens);
+        }
+    }
+
+    fn strength(&self) -> usize {
+        (self.n + 1) *
+            self.lenses.iter().enumerate()
+                .map(|(position, lens)| (position + 1) * lens.strength)
+                .sum::<usize>()
+    }
+}
+
+pub struct Boxes(Vec<Box>);
+
+impl Default for Boxes {
+    fn default() -> Self {
+        Boxes((0..=255).map(|n| Box{ n, lenses: Vec::new() }).collect())
+    }
+}
+
+impl Boxes {
+    fn apply_instruction(&mut self, ins: &Instruction) {
+        let Boxes(ref mut boxes) = self;
+        match ins {
+            AddLens{ label, strength, hash } =>
+                boxes[*hash].insert_lens(label, *strength),
+            RemoveLens{ label, hash } =>
+                boxes[*hash].remove_lens(label),
+        }
+    }
+
+    fn strength(&self) -> usize {
+        let Boxes(boxes) = self;
+        boxes.iter().map(|b| b.strength()).sum()
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Vec<Step>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Step>, ParseError> {
+        let steps = parse::lines(input).map(read_steps).collect::<Result<Vec<_>, _>>()?;
+        Ok(steps.into_iter().flatten().collect())
+    }
+
+    fn part1(&self, steps: &Vec<Step>) -> usize {
+        steps.iter().map(|Step(s, _)| hash::ascii(s)).sum()
+    }
+
+    fn part2(&self, steps: &Vec<Step>) -> usize {
+        let mut boxes = Boxes::default();
+        steps.iter().for_each(|Step(_, ins)| boxes.apply_instruction(ins));
+        boxes.strength()
+    }
+}
+
+// The input is a single long line, so streaming goes by steps instead.
+// Part 2 keeps the lenses in the boxes, one per label at most.
+impl Streaming for Solver {
+    type Record = Step;
+    type State1 = usize;
+    type State2 = Boxes;
+
+    const SEPARATOR: u8 = b',';
+
+    fn record(&self, line: Line) -> Result<Step, ParseError> {
+        read_step(&line, line.text)
+    }
+
+    fn add1(&self, sum: &mut usize, Step(s, _): &Step) {
+        *sum += hash::ascii(s)
+    }
+
+    fn add2(&self, boxes: &mut Boxes, Step(_, ins): &Step) {
+        boxes.apply_instruction(ins)
+    }
+
+    fn answer1(&self, sum: usize) -> usize {
+        sum
+    }
+
+    fn answer2(&self, boxes: Boxes) -> usize {
+        boxes.strength()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn stream(args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    puzzle::solve_stream(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run_stream(DAY, stream, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 1320);
+        assert_eq!(Solver.part2(&model), 145);
+    }
+
+    // Errors point at the same place whether the input is streamed or not.
+    #[test]
+    fn streaming() {
+        assert_eq!(puzzle::stream_answers(&Solver, &mut EXAMPLE.as_bytes()).unwrap(), (1320, 145));
+        let bad = "rn=1,cm-\r\nqp=3,cm=x,qp-\n";
+        let e = puzzle::stream_answers(&Solver, &mut bad.as_bytes()).unwrap_err();
+        let e = e.downcast::<ParseError>().unwrap();
+        assert_eq!(*e, Solver.parse(bad).err().unwrap());
+        assert_eq!((e.line, e.column), (2, 9));
+    }
+
+    // Removing every lens at the end leaves nothing to focus.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ steps: 300, labels: 40, ..Default::default() };
+        for seed in 0..20 {
+            let input = gen.generate(&mut Rng::new(seed));
+            let steps = Solver.parse(&input).unwrap();
+            let removals = steps.iter()
+                .map(|Step(s, _)| format!(",{}-", s.split(['=', '-']).next().unwrap()))
+                .collect::<String>();
+            let cleared = Solver.parse(&format!("{}{}", input.trim_end(), removals)).unwrap();
+            assert_eq!(Solver.part2(&cleared), 0);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// Random inputs: a contraption with mirrors and splitters.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the contraption (default 110)"),
+    Opt::value("mirrors", "p", "chance of a mirror or splitter at each tile (default 0.1)"),
+];
+
+const DEVICES: &[char] = &['/', '\\', '|', '-'];
+
+pub struct Generator {
+    pub size: usize,
+    pub mirrors: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 110, mirrors: 0.1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 1..)?,
+            mirrors: args.value_in("mirrors", d.mirrors, 0.0..=1.0)?,
+        })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut grid = Grid::filled(self.size, self.size, '.');
+        for pos in grid.positions() {
+            if rng.chance(self.mirrors) {
+                grid[pos] = rng.choose(DEVICES);
+            }
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
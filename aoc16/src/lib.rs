@@ -0,0 +1,403 @@
+// Advent-of-Code 2023
+// Day 16
+// Author: Matthias Blume
+
+use std::ops::{Index,IndexMut};
+use std::cmp::max;
+
+use util::animate::{self, Frame, Simulation};
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::direction::{Direction, screen::*};
+use util::grid::Grid;
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::{self, Color, Picture};
+
+pub mod generate;
+
+pub const DAY: usize = 16;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("render", "file", "draw the energized tiles (of the best start with --part 2) to the given .svg or .ppm file"),
+    animate::ANIMATE,
+    animate::FPS,
+    animate::FRAMES,
+];
+
+type Pos = (i64, i64);
+
+#[derive(Copy, Clone)]
+enum TileConfig {
+    Empty,
+    DiagDown,
+    DiagUp,
+    Hor,
+    Vert,
+}
+use TileConfig::*;
+
+impl TileConfig {
+    fn from(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Empty),
+            '\\' => Some(DiagDown),
+            '/' => Some(DiagUp),
+            '-' => Some(Hor),
+            '|' => Some(Vert),
+            _ => None,
+        }
+    }
+}
+
+// Fields that are true indicate outgoing light.
+struct TileState {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+}
+
+impl TileState {
+    fn new() -> TileState {
+        TileState { up: false, down: false, left: false, right: false }
+    }
+}
+
+// Allow a TileState to be indexed by a Direction.
+impl Index<Direction> for TileState {
+    type Output = bool;
+    fn index(&self, index: Direction) -> &Self::Output {
+        match index {
+            Up => &self.up,
+            Down => &self.down,
+            Left => &self.left,
+            Right => &self.right,
+        }
+    }
+}
+
+// Allow a TileState to be mutably indexed by a Direction.
+impl IndexMut<Direction> for TileState {
+    fn index_mut(&mut self, index: Direction) -> &mut Self::Output {
+        match index {
+            Up => &mut self.up,
+            Down => &mut self.down,
+            Left => &mut self.left,
+            Right => &mut self.right,
+        }
+    }
+}
+
+type State = Grid<TileState>;
+
+pub struct Config {
+    height: usize,
+    width: usize,
+    board: Grid<TileConfig>,
+}
+
+impl Config {
+    fn new(board: Grid<TileConfig>) -> Self {
+        Config{ height: board.height(), width: board.width(), board }
+    }
+
+    // Fresh State with same dimensions as the Config.
+    fn new_state(&self) -> State {
+        self.board.map(|_| TileState::new())
+    }
+
+    // Get individual tile configuration unless position is out of bounds.
+    fn get(&self, p: Pos) -> Option<TileConfig> {
+        self.board.index_of(p).map(|p| self.board[p])
+    }
+}
+
+// Update state to indicate that light is going out in the given
+// direction.  If this update changes the state, then propagate
+// light in that direction.
+fn maybe_propagate(d: Direction, p: Pos, config: &Config, state: &mut State) {
+    let indicator = &mut state[(p.0 as usize, p.1 as usize)][d];
+    let was_already_set = *indicator;
+    *indicator = true;
+    if !was_already_set { propagate(d, d.step(p), config, state) }
+}
+
+// The direction(s) in which light leaves a tile that it enters going in
+// the given direction.
+fn outgoing(d: Direction, cfg: TileConfig) -> (Direction, Option<Direction>) {
+    match (d, cfg) {
+        (Down, DiagDown) | (Up, DiagUp) | (Right, Hor | Empty) =>
+            (Right, None),
+        (Down, DiagUp) | (Up, DiagDown) | (Left, Hor | Empty) =>
+            (Left, None),
+        (Right, DiagUp) | (Left, DiagDown) | (Up, Vert | Empty) =>
+            (Up, None),
+        (Left, DiagUp) | (Right, DiagDown) | (Down, Vert | Empty) =>
+            (Down, None),
+        (Up | Down, Hor) =>
+            (Left, Some(Right)),
+        (Left | Right, Vert) =>
+            (Up, Some(Down)),
+    }
+}
+
+// Propagate light into a position, going in the given direction.
+fn propagate(d: Direction, p: Pos, config: &Config, state: &mut State) {
+    if let Some(cfg) = config.get(p) {
+        let (d1, maybe_d2) = outgoing(d, cfg);
+        maybe_propagate(d1, p, config, state);
+        if let Some(d2) = maybe_d2 { maybe_propagate(d2, p, config, state) };
+    }
+}
+
+// Tile is active if light is flowing out into any direction.
+fn is_active(s: &&TileState) -> bool {
+    s.up || s.down || s.left || s.right
+}
+
+// The state after propagating light from the given position, starting
+// from a fresh state.
+fn light(d: Direction, p: Pos, config: &Config) -> State {
+    let mut state: State = config.new_state();
+    propagate(d, p, config, &mut state);
+    state
+}
+
+// Count active tiles after propagating light from the given position,
+// starting from a fresh state.
+fn num_active(d: Direction, p: Pos, config: &Config) -> usize {
+    light(d, p, config).iter().filter(|(_, s)| is_active(s)).count()
+}
+
+// Maximizes active tiles, starting from a side, horizontally.
+fn max_hor_active(config: &Config) -> usize {
+    // From left edge at every row:
+    let m = (0..config.height)
+        .map(|i| num_active(Right, (i as i64, 0), config))
+        .fold(0, max);
+    // From right edge at every row:
+    (0..config.height)
+        .map(|i| num_active(Left, (i as i64, config.width as i64 - 1), config))
+        .fold(m, max)
+}
+
+// Maximizes active tiles, starting from top or bottom, vertically.
+fn max_vert_active(config: &Config) -> usize {
+    // From top at every column:
+    let m = (0..config.width)
+        .map(|j| num_active(Down, (0, j as i64), config))
+        .fold(0, max);
+    // From bottom at every column:
+    (0..config.width)
+        .map(|j| num_active(Up, (config.height as i64 - 1, j as i64), config))
+        .fold(m, max)
+}
+
+// Maximizes active tiles, starting from any edge in any direction.
+fn max_hor_or_vert_active(config: &Config) -> usize {
+    max_hor_active(config).max(max_vert_active(config))
+}
+
+// Where light can enter from the edges, in the order tried above.
+fn edge_starts(config: &Config) -> Vec<(Direction, Pos)> {
+    let (h, w) = (config.height as i64, config.width as i64);
+    (0..h).map(|i| (Right, (i, 0)))
+        .chain((0..h).map(|i| (Left, (i, w - 1))))
+        .chain((0..w).map(|j| (Down, (0, j))))
+        .chain((0..w).map(|j| (Up, (h - 1, j))))
+        .collect()
+}
+
+// The active tiles for light entering at the given position, with the
+// mirrors and splitters drawn on top.
+fn picture(config: &Config, d: Direction, p: Pos) -> Picture {
+    let state = light(d, p, config);
+    let mut picture = Picture::from(&config.board, |t| match t {
+        Empty => Color::WHITE,
+        _ => Color::GRAY,
+    });
+    picture.highlight(state.iter().filter(|(_, s)| is_active(s)).map(|(p, _)| p), Color::YELLOW);
+    for ((i, j), t) in config.board.iter() {
+        let (r, c) = (i as f64, j as f64);
+        match t {
+            Empty => (),
+            DiagDown => picture.line([(r, c), (r + 1.0, c + 1.0)], Color::BLACK),
+            DiagUp => picture.line([(r + 1.0, c), (r, c + 1.0)], Color::BLACK),
+            Hor => picture.line([(r + 0.5, c), (r + 0.5, c + 1.0)], Color::BLACK),
+            Vert => picture.line([(r, c + 0.5), (r + 1.0, c + 0.5)], Color::BLACK),
+        }
+    }
+    picture
+}
+
+// The light spreading one tile at a time, for the animation: the beams'
+// heads are where light is about to enter, going in some direction.
+struct Beams {
+    config: Config,
+    state: State,
+    heads: Vec<(Direction, Pos)>,
+    steps: usize,
+}
+
+impl Beams {
+    fn new(config: Config, part: Option<Part>) -> Self {
+        let (d, p) = start(&config, part);
+        Beams{ state: config.new_state(), config, heads: vec![(d, p)], steps: 0 }
+    }
+}
+
+impl Simulation for Beams {
+    fn frame(&self) -> Frame {
+        let mut cells = self.config.board.map(|t| match t {
+            Empty => ('.', Color(90, 90, 90)),
+            DiagDown => ('\\', Color::GRAY),
+            DiagUp => ('/', Color::GRAY),
+            Hor => ('-', Color::GRAY),
+            Vert => ('|', Color::GRAY),
+        });
+        for (p, _) in self.state.iter().filter(|(_, s)| is_active(s)) {
+            cells[p].1 = Color::YELLOW;
+        }
+        for &(_, p) in &self.heads {
+            if let Some(p) = self.config.board.index_of(p) {
+                cells[p].1 = Color::RED;
+            }
+        }
+        let active = self.state.iter().filter(|(_, s)| is_active(s)).count();
+        Frame{ cells, caption: format!("step {}, {} tiles energized", self.steps, active) }
+    }
+
+    // Light leaving a tile in a direction it already left in has been
+    // followed before.
+    fn step(&mut self) -> bool {
+        let mut heads = Vec::new();
+        for (d, p) in self.heads.drain(..) {
+            let Some(cfg) = self.config.get(p) else { continue };
+            let (d1, maybe_d2) = outgoing(d, cfg);
+            for d in [Some(d1), maybe_d2].into_iter().flatten() {
+                let out = &mut self.state[(p.0 as usize, p.1 as usize)][d];
+                if !*out {
+                    *out = true;
+                    heads.push((d, d.step(p)));
+                }
+            }
+        }
+        self.heads = heads;
+        self.steps += 1;
+        !self.heads.is_empty()
+    }
+}
+
+// Part 1's start, or with --part 2 the best one.
+fn start(config: &Config, part: Option<Part>) -> (Direction, Pos) {
+    match part {
+        Some(Part::Two) => edge_starts(config).into_iter()
+            .max_by_key(|&(d, p)| num_active(d, p, config))
+            .expect("edge"),
+        _ => (Right, (0, 0)),
+    }
+}
+
+// Part 1's light, or with --part 2 the light from the best start.
+fn draw(config: &Config, part: Option<Part>, file: &str) -> RunResult {
+    let (d, p) = start(config, part);
+    picture(config, d, p).write(file)
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Config;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Config, ParseError> {
+        Ok(Config::new(Grid::parse(input, TileConfig::from, "tile")?))
+    }
+
+    fn part1(&self, config: &Config) -> usize {
+        num_active(Right, (0, 0), config)
+    }
+
+    fn part2(&self, config: &Config) -> usize {
+        max_hor_or_vert_active(config)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    animate::play(args, || Ok(Beams::new(Solver.parse(input)?, args.part)))?;
+    let answers = puzzle::solve(&Solver, args, input)?;
+    if let Some(file) = render::target(args)? {
+        draw(&Solver.parse(input)?, args.part, &file)?;
+    }
+    Ok(answers)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 46);
+        assert_eq!(Solver.part2(&model), 51);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 20, ..Default::default() };
+        for seed in 0..20 {
+            let config = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let (one, two) = (Solver.part1(&config), Solver.part2(&config));
+            assert!(1 <= one && one <= two && two <= 20 * 20);
+        }
+        let empty = generate::Generator{ size: 20, mirrors: 0.0 };
+        let config = Solver.parse(&empty.generate(&mut Rng::new(0))).unwrap();
+        assert_eq!((Solver.part1(&config), Solver.part2(&config)), (20, 20));
+    }
+
+    #[test]
+    fn edge_starts_cover_part2() {
+        let config = Solver.parse(EXAMPLE).unwrap();
+        let starts = edge_starts(&config);
+        assert_eq!(starts.len(), 2 * (10 + 10));
+        let best = starts.iter().map(|&(d, p)| num_active(d, p, &config)).max();
+        assert_eq!(best, Some(Solver.part2(&config)));
+    }
+
+    #[test]
+    fn beams_step_by_step() {
+        let config = Solver.parse(EXAMPLE).unwrap();
+        let part2 = Solver.part2(&config);
+        let mut beams = Beams::new(config, None);
+        while beams.step() {}
+        assert_eq!(beams.state.iter().filter(|(_, s)| is_active(s)).count(), 46);
+        let config = Solver.parse(EXAMPLE).unwrap();
+        let mut beams = Beams::new(config, Some(Part::Two));
+        while beams.step() {}
+        assert_eq!(beams.state.iter().filter(|(_, s)| is_active(s)).count(), part2);
+    }
+}
@@ -0,0 +1,45 @@
+// Random inputs: a map of heat loss per city block.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the map (default 141)"),
+    Opt::value("max", "n", "largest heat loss of a block, up to 9 (default 9)"),
+];
+
+pub struct Generator {
+    pub size: usize,
+    pub max: u32,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 141, max: 9 }
+    }
+}
+
+impl Generator {
+    // Ultra crucibles need at least four blocks in each direction to
+    // get anywhere.
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 5..)?,
+            max: args.value_in("max", d.max, 1..=9)?,
+        })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut grid = Grid::filled(self.size, self.size, 0);
+        for pos in grid.positions() {
+            grid[pos] = rng.range(1..=self.max as i64);
+        }
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
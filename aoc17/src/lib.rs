@@ -0,0 +1,273 @@
+// Advent-of-Code 2023
+// Day 17
+// Author: Matthias Blume
+
+use std::cell::RefCell;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::direction::Direction;
+use util::grid::{Grid, Pos};
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::{self, Color, Picture};
+use util::search;
+
+pub mod generate;
+pub mod using_dijkstra;
+
+pub const DAY: usize = 17;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("render", "file", "draw the best route (of part 2 with --part 2) to the given .svg or .ppm file"),
+];
+
+#[derive(Copy, Clone)]
+struct Limits {
+    at_least: Option<usize>,
+    at_most: Option<usize>,
+}
+
+impl Limits {
+    fn permit(self, old: Direction, new: Direction, dsteps: usize) -> bool {
+        if let Some(at_least) = self.at_least {
+            if old != new && dsteps < at_least { return false };
+        }
+        if let Some(at_most) = self.at_most {
+            if old == new && dsteps >= at_most { return false };
+        }
+        true
+    }
+}
+            
+#[derive(PartialEq, Eq, Hash, Copy, Clone)]
+struct State {
+    i: usize,
+    j: usize,
+    dinfo: Option<(Direction, usize)>,
+}
+
+impl State {
+    fn next(&self, d: Direction, board: &Board, limits: Limits) -> Option<Self> {
+        let dinfo =
+            if let Some((old_d, dsteps)) = self.dinfo {
+                if old_d == d.reverse() { return None }
+                if !limits.permit(old_d, d, dsteps) { return None }
+                let dsteps = if old_d == d { dsteps + 1 } else { 1 };
+                Some((d, dsteps))
+            } else {
+                Some((d, 1))
+            };
+        let (i, j) = board.losses.go((self.i, self.j), d)?;
+        Some(State{ i, j, dinfo })
+    }
+}
+
+pub struct Board {
+    height: usize,
+    width: usize,
+    losses: Grid<u64>,
+}
+
+impl Board {
+    fn new(losses: Grid<u64>) -> Self {
+        Board{ height: losses.height(), width: losses.width(), losses }
+    }
+
+    fn from(input: &str) -> Result<Self, ParseError> {
+        let losses = Grid::parse(input, |c| c.to_digit(10).map(|d| d as u64), "digit")?;
+        Ok(Self::new(losses))
+    }
+
+    fn with_loss(&self, state: State) -> (State, u64) {
+        (state, self.losses[(state.i, state.j)])
+    }
+    
+    fn successors(&'_ self, state: State, limits: Limits)
+                  -> impl IntoIterator<Item = (State, u64)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(
+                move |d| state.next(d, self, limits)
+                    .map(|state| self.with_loss(state)))
+    }
+
+    fn is_goal(&self, state: State) -> bool {
+        state.i == self.height - 1 && state.j == self.width - 1
+    }
+}
+
+// The least heat loss and the blocks along a route with that loss,
+// start and goal included, if there is a route at all.
+fn best_route(board: &Board, limits: Limits) -> Option<(u64, Vec<Pos>)> {
+    search::dijkstra(State{ i: 0, j: 0, dinfo: None },
+                     |&state| board.successors(state, limits),
+                     |&state| board.is_goal(state))
+        .map(|(path, loss)| (loss, path.iter().map(|s| (s.i, s.j)).collect()))
+}
+
+fn find_best(board: &Board, limits: Limits) -> Result<u64, &'static str> {
+    best_route(board, limits).map(|(loss, _)| loss).ok_or("no solution")
+}
+
+// Heat loss in shades of gray, darker for more, with the given route on
+// top.
+fn picture(board: &Board, route: Vec<Pos>) -> Picture {
+    let mut picture = Picture::from(&board.losses, |&loss| {
+        let shade = 255 - 20 * loss.min(9) as u8;
+        Color(shade, shade, shade)
+    });
+    picture.path(route, Color::RED);
+    picture
+}
+
+const PART1_LIMITS: Limits = Limits{ at_least: None, at_most: Some(3) };
+const PART2_LIMITS: Limits = Limits{ at_least: Some(4), at_most: Some(10) };
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Board;
+    type Answer1 = Result<u64, &'static str>;
+    type Answer2 = Result<u64, &'static str>;
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        Board::from(input)
+    }
+
+    fn part1(&self, board: &Board) -> Result<u64, &'static str> {
+        find_best(board, PART1_LIMITS)
+    }
+
+    fn part2(&self, board: &Board) -> Result<u64, &'static str> {
+        find_best(board, PART2_LIMITS)
+    }
+}
+
+// Solves like Solver, and draws the route found for the given part on
+// the way, so the picture shows the route the answer came from.  Without
+// a route, the picture shows just the map.
+struct Rendering {
+    part: Part,
+    picture: RefCell<Option<Picture>>,
+}
+
+impl Rendering {
+    fn find_best(&self, board: &Board, part: Part, limits: Limits) -> Result<u64, &'static str> {
+        let (loss, route) = match best_route(board, limits) {
+            Some((loss, route)) => (Ok(loss), route),
+            None => (Err("no solution"), Vec::new()),
+        };
+        if part == self.part {
+            self.picture.replace(Some(picture(board, route)));
+        }
+        loss
+    }
+}
+
+impl Puzzle for Rendering {
+    type Model = Board;
+    type Answer1 = Result<u64, &'static str>;
+    type Answer2 = Result<u64, &'static str>;
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        Board::from(input)
+    }
+
+    fn part1(&self, board: &Board) -> Result<u64, &'static str> {
+        self.find_best(board, Part::One, PART1_LIMITS)
+    }
+
+    fn part2(&self, board: &Board) -> Result<u64, &'static str> {
+        self.find_best(board, Part::Two, PART2_LIMITS)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let Some(file) = render::target(args)? else {
+        return puzzle::solve(&Solver, args, input)
+    };
+    let part = args.part.unwrap_or(Part::One);
+    let rendering = Rendering{ part, picture: RefCell::new(None) };
+    let answers = puzzle::solve(&rendering, args, input)?;
+    if let Some(picture) = rendering.picture.take() {
+        picture.write(&file)?;
+    }
+    Ok(answers)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), Ok(102));
+        assert_eq!(Solver.part2(&model), Ok(94));
+    }
+
+    // The route adds up to the least heat loss, not counting the start.
+    #[test]
+    fn example_routes() {
+        let board = Solver.parse(EXAMPLE).unwrap();
+        for (limits, loss) in [(PART1_LIMITS, 102), (PART2_LIMITS, 94)] {
+            let (best, route) = best_route(&board, limits).unwrap();
+            assert_eq!(best, loss);
+            assert_eq!(route.first(), Some(&(0, 0)));
+            assert_eq!(route.last(), Some(&(12, 12)));
+            assert_eq!(route[1..].iter().map(|&p| board.losses[p]).sum::<u64>(), loss);
+        }
+    }
+
+    // A crucible cannot go four blocks in a straight line, so part 1 has
+    // no route along a single row, while part 2 has one.
+    #[test]
+    fn no_route() {
+        let board = Solver.parse("11111\n").unwrap();
+        assert_eq!(Solver.part1(&board), Err("no solution"));
+        assert_eq!(using_dijkstra::Solver.part1(&board), Err("no solution"));
+        assert_eq!(Solver.part2(&board), Ok(4));
+        assert_eq!(using_dijkstra::Solver.part2(&board), Ok(4));
+    }
+
+    // Every block costs at least one, and with all costs being one, any
+    // path without detours is a best one.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 8, ..Default::default() };
+        for seed in 0..20 {
+            let board = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            assert!(Solver.part1(&board).unwrap() >= 14);
+            assert!(Solver.part2(&board).unwrap() >= 14);
+        }
+        let flat = generate::Generator{ size: 8, max: 1 };
+        let board = Solver.parse(&flat.generate(&mut Rng::new(0))).unwrap();
+        assert_eq!((Solver.part1(&board), Solver.part2(&board)), (Ok(14), Ok(14)));
+    }
+}
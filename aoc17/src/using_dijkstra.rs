@@ -0,0 +1,52 @@
+// Alternative solution using the dijkstra function
+// from the pathfinding crate.  Kept around to cross-check
+// the search in util, which the main solution uses.
+
+use pathfinding::prelude::dijkstra;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, RunResult};
+use util::parse::ParseError;
+use util::puzzle::{self, Puzzle, SolveResult};
+
+use super::{Board, Limits, State, PART1_LIMITS, PART2_LIMITS};
+
+fn find_best(board: &Board, limits: Limits) -> Result<u64, &'static str> {
+    dijkstra(&State{ i: 0, j: 0, dinfo: None },
+             |&state| board.successors(state, limits),
+             |&state| board.is_goal(state))
+        .map(|(_, loss)| loss)
+        .ok_or("no solution")
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Board;
+    type Answer1 = Result<u64, &'static str>;
+    type Answer2 = Result<u64, &'static str>;
+
+    fn parse(&self, input: &str) -> Result<Board, ParseError> {
+        Board::from(input)
+    }
+
+    fn part1(&self, board: &Board) -> Result<u64, &'static str> {
+        find_best(board, PART1_LIMITS)
+    }
+
+    fn part2(&self, board: &Board) -> Result<u64, &'static str> {
+        find_best(board, PART2_LIMITS)
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(super::DAY, solve, args)
+}
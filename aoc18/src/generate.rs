@@ -0,0 +1,88 @@
+// Random inputs: dig plans whose trench is a simple loop.  The hidden
+// plan in the colors follows the same loop on a much larger scale.
+
+use util::cli::{Args, Opt, UsageError};
+use util::direction::Direction;
+use util::random::Rng;
+
+use Direction::*;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "number of possible corners across the lagoon (default 40)"),
+    Opt::value("fill", "p", "share of the area that the trench tries to enclose (default 0.5)"),
+    Opt::value("gap", "n", "maximum distance between neighboring corners (default 10)"),
+];
+
+// Largest distance that fits the five hex digits of a color.
+const MAX_HEX: i64 = 0xfffff;
+
+pub struct Generator {
+    pub size: usize,
+    pub fill: f64,
+    pub gap: i64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 40, fill: 0.5, gap: 10 }
+    }
+}
+
+// Positions of the corners along one axis, with random gaps between
+// them.
+fn coordinates(rng: &mut Rng, n: usize, gap: i64) -> Vec<i64> {
+    (0..=n).scan(0, |x, _| {
+        let here = *x;
+        *x += rng.range(1..=gap);
+        Some(here)
+    }).collect()
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            size: args.value_in("size", d.size, 1..=1000)?,
+            fill: args.value_in("fill", d.fill, 0.0..=1.0)?,
+            gap: args.value_in("gap", d.gap, 1..=MAX_HEX / 1000)?,
+        })
+    }
+
+    // The loop is made on a lattice, whose lines are then spread out
+    // differently for the two plans.  Spreading keeps the order of the
+    // lines, so the trench stays a simple loop.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let squares = ((self.size * self.size) as f64 * self.fill).max(1.0) as usize;
+        let ((r, c), steps) = rng.closed_loop(self.size, self.size, squares);
+        let near = [coordinates(rng, self.size, self.gap), coordinates(rng, self.size, self.gap)];
+        let far_gap = MAX_HEX / self.size as i64;
+        let far = [coordinates(rng, self.size, far_gap), coordinates(rng, self.size, far_gap)];
+        let mut runs: Vec<(Direction, usize)> = Vec::new();
+        for d in steps {
+            match runs.last_mut() {
+                Some((e, n)) if *e == d => *n += 1,
+                _ => runs.push((d, 1)),
+            }
+        }
+        let mut p = (r, c);
+        let mut out = String::new();
+        for (d, n) in runs {
+            // Rows are axis 0, columns axis 1.
+            let (axis, from, to) = match d {
+                North => (0, p.0, p.0 - n),
+                South => (0, p.0, p.0 + n),
+                West => (1, p.1, p.1 - n),
+                East => (1, p.1, p.1 + n),
+            };
+            let distance = |coords: &[Vec<i64>; 2]| coords[axis][from].abs_diff(coords[axis][to]);
+            let (letter, digit) = match d { East => ('R', 0), South => ('D', 1), West => ('L', 2), North => ('U', 3) };
+            out += &format!("{} {} (#{:05x}{})\n", letter, distance(&near), distance(&far), digit);
+            if axis == 0 { p.0 = to } else { p.1 = to }
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
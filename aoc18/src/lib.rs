@@ -0,0 +1,280 @@
+// Advent-of-Code 2023
+// Day 18
+// Author: Matthias Blume
+
+use std::collections::BTreeMap;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::direction::Direction;
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::{self, Color, Picture};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 18;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("render", "file", "draw the trench (of part 2 with --part 2) to the given .svg or .ppm file"),
+];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Tile {
+    NE,
+    SE,
+    NW,
+    SW,
+    NS,
+}
+use Tile::*;
+
+use Direction::*;
+
+type Row = BTreeMap<i64, Tile>;
+type Board = BTreeMap<i64, Row>;
+type Pos = (i64, i64);
+
+struct Step(Direction, i64);
+
+impl Step {
+    fn from_color(line: &Line, col: &str) -> Result<Self, ParseError> {
+        let bad = || line.error(col, "bad encoded color");
+        let hex = col.strip_prefix("(#")
+            .and_then(|s| s.strip_suffix(')'))
+            .filter(|s| s.len() == 6 && s.is_ascii())
+            .ok_or_else(bad)?;
+        let dir =
+            match &hex[5..6] {
+                "0" => East,
+                "1" => South,
+                "2" => West,
+                "3" => North,
+                _ => return Err(bad()),
+            };
+        let dist = i64::from_str_radix(&hex[0..5], 16).map_err(|_| bad())?;
+        Ok(Step(dir, dist))
+    }
+
+    fn from(line: &Line, part: Part) -> Result<Self, ParseError> {
+        match line.text.split_whitespace().boxed()[..] {
+            [dir, dist, col] => match part {
+                Part::One =>
+                    Ok(Step(line.parse(dir, "direction")?,
+                            line.parse(dist, "distance")?)),
+                Part::Two =>
+                    Step::from_color(line, col),
+            },
+            _ => Err(line.fail("bad step")),
+        }
+    }
+
+    fn next(&self, p: Pos) -> Pos {
+        self.0.step_n(p, self.1)
+    }
+    
+    fn to_board(steps: &[Self]) -> Board {
+        let initial = (0, 0);
+        let mut board: Board = BTreeMap::new();
+        let mut prev_d = steps[steps.len() - 1].0;
+        let mut cur = initial;
+        for step in steps.iter() {
+            let prev = cur;
+            cur = step.next(prev);
+            let corner = match (prev_d, step.0) {
+                (South, East) | (West, North) => NE,
+                (South, West) | (East, North) => NW,
+                (North, East) | (West, South) => SE,
+                (North, West) | (East, South) => SW,
+                _ => panic!("not a corner"),
+            };
+            board.entry(prev.0).or_default().insert(prev.1, corner);
+            prev_d = step.0;
+        }
+        board
+    }
+}
+
+fn register_ns(row: &mut Row, j: &i64) {
+    if !row.contains_key(j) {
+        row.insert(*j, NS);
+    }
+}
+
+// Calculate area inside loop on an individual row.
+// Insert NS tiles into next row.
+fn row_area(row: &Row) -> (i64, Row) {
+    let mut skipped_row = BTreeMap::new();
+    let area = row.keys()
+        .fold((false, false, 0, 0), |(inside, was_north, n, j0), j| {
+            let tile = row.get(j).expect("tile");
+            match tile {
+                NS => {
+                    register_ns(&mut skipped_row, j);
+                    (!inside, false, if inside { n + (j - j0) } else { n + 1 }, *j)
+                },
+                NE => (inside, true, if inside { n + (j - j0) } else { n + 1 }, *j),   // L---
+                SE => {
+                    register_ns(&mut skipped_row, j);
+                    (inside, false, if inside { n + (j - j0) } else { n + 1 }, *j)
+                },  // F---
+                NW => (was_north == inside, false, n + (j - j0), *j),  // L---J  vs. F---J
+                SW => {
+                    register_ns(&mut skipped_row, j);
+                    (was_north != inside, false, n + (j - j0), *j)
+                },  // F---7  vs. L---7
+            }
+        })
+        .2;
+    (area, skipped_row)
+}
+
+// Count area inside the loop on a LoopBoard.
+fn board_area(board: &Board) -> i64 {
+    let mut prev_key = -1;
+    let mut skipped_row = BTreeMap::new();
+    let mut area = 0;
+    for key in board.keys() {
+        let num_skipped = key - prev_key - 1;
+        if num_skipped > 0 {
+            let line_area = row_area(&skipped_row).0;
+            area += num_skipped * line_area;
+        }
+        let row = board.get(key).expect("row");
+        for (&k, &v) in row.iter() {
+            skipped_row.insert(k, v);
+        }
+        let (row_area, new_skipped) = row_area(&skipped_row);
+        area += row_area;
+        skipped_row = new_skipped;
+        prev_key = *key;
+    }
+    area
+}
+
+// Larger dig plans get scaled down to fit a picture this many cells
+// across.
+const MAX_PICTURE_SIZE: i64 = 1000;
+
+// The outline of the trench, through the middle of the cubes dug out.
+fn picture(steps: &[Step]) -> Picture {
+    let corners = steps.iter()
+        .scan((0, 0), |p, step| { *p = step.next(*p); Some(*p) })
+        .collect::<Vec<_>>();
+    let (top, left) = corners.iter().fold((0, 0), |(r, c), p| (r.min(p.0), c.min(p.1)));
+    let (bottom, right) = corners.iter().fold((0, 0), |(r, c), p| (r.max(p.0), c.max(p.1)));
+    let size = (bottom - top + 1).max(right - left + 1);
+    let scale = if size > MAX_PICTURE_SIZE { MAX_PICTURE_SIZE as f64 / size as f64 } else { 1.0 };
+    let cells = |n: i64| (n as f64 * scale).ceil() as usize;
+    let mut picture = Picture::new(cells(bottom - top + 1), cells(right - left + 1), Color::WHITE);
+    let points = corners.iter()
+        .map(|&(r, c)| (((r - top) as f64 + 0.5) * scale, ((c - left) as f64 + 0.5) * scale));
+    picture.polygon(points, Color::BLUE);
+    picture
+}
+
+// The dig plan as read for either part.
+pub struct Plan {
+    part1: Vec<Step>,
+    part2: Vec<Step>,
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Plan;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Plan, ParseError> {
+        let steps = |part| parse::lines(input)
+            .map(|line| Step::from(&line, part))
+            .collect::<Result<Vec<_>, _>>();
+        let plan = Plan{ part1: steps(Part::One)?, part2: steps(Part::Two)? };
+        if plan.part1.is_empty() {
+            return Err(parse::eof_error(input, "empty dig plan"))
+        }
+        Ok(plan)
+    }
+
+    fn part1(&self, plan: &Plan) -> i64 {
+        board_area(&Step::to_board(&plan.part1))
+    }
+
+    fn part2(&self, plan: &Plan) -> i64 {
+        board_area(&Step::to_board(&plan.part2))
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let answers = puzzle::solve(&Solver, args, input)?;
+    if let Some(file) = render::target(args)? {
+        let plan = Solver.parse(input)?;
+        let steps = if args.part == Some(Part::Two) { &plan.part2 } else { &plan.part1 };
+        picture(steps).write(&file)?;
+    }
+    Ok(answers)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 62);
+        assert_eq!(Solver.part2(&model), 952408144115);
+    }
+
+    // The lagoon by the shoelace formula and Pick's theorem: the
+    // interior points plus the ones on the trench.
+    fn lagoon(steps: &[Step]) -> i64 {
+        let mut p = (0, 0);
+        let (mut twice_area, mut trench) = (0, 0);
+        for step in steps {
+            let q = step.next(p);
+            twice_area += p.0 * q.1 - q.0 * p.1;
+            trench += step.1;
+            p = q;
+        }
+        twice_area.abs() / 2 + trench / 2 + 1
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 15, ..Default::default() };
+        for seed in 0..20 {
+            let plan = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            assert_eq!(Solver.part1(&plan), lagoon(&plan.part1));
+            assert_eq!(Solver.part2(&plan), lagoon(&plan.part2));
+        }
+    }
+}
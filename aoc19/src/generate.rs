@@ -0,0 +1,94 @@
+// Random inputs: a system of workflows and a list of parts.  Workflows
+// only send parts on to workflows further down, so there are no
+// cycles, and the depth bounds how many workflows a part goes through.
+
+use std::collections::HashSet;
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("workflows", "n", "number of workflows (default 550)"),
+    Opt::value("depth", "n", "maximum number of workflows a part goes through (default 12)"),
+    Opt::value("rules", "n", "maximum number of rules in a workflow (default 4)"),
+    Opt::value("parts", "n", "number of parts (default 200)"),
+];
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator {
+    pub workflows: usize,
+    pub depth: usize,
+    pub rules: usize,
+    pub parts: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ workflows: 550, depth: 12, rules: 4, parts: 200 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            workflows: args.value_in("workflows", d.workflows, 1..)?,
+            depth: args.value_in("depth", d.depth, 2..)?,
+            rules: args.value_in("rules", d.rules, 0..)?,
+            parts: args.value_in("parts", d.parts, 0..)?,
+        })
+    }
+
+    // Workflow "in" is on level 0, the others on random levels below
+    // the depth.  Names are sorted by level.
+    fn names(&self, rng: &mut Rng) -> Vec<(usize, String)> {
+        let mut used = HashSet::from([String::from("in")]);
+        let mut names = vec![(0, String::from("in"))];
+        while names.len() < self.workflows {
+            let len = 2 + rng.index(3);
+            let name = rng.word(len, LETTERS);
+            if used.insert(name.clone()) {
+                names.push((1 + rng.index(self.depth - 1), name));
+            }
+        }
+        names.sort();
+        names
+    }
+
+    // Somewhere further down, or a final decision.
+    fn action(rng: &mut Rng, below: &[(usize, String)]) -> String {
+        if !below.is_empty() && rng.chance(0.7) {
+            below[rng.index(below.len())].1.clone()
+        } else {
+            String::from(if rng.chance(0.5) { "A" } else { "R" })
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let names = self.names(rng);
+        let mut out = String::new();
+        for (level, name) in &names {
+            let below = &names[names.partition_point(|(l, _)| l <= level)..];
+            let mut rules = (0..rng.index(self.rules + 1))
+                .map(|_| {
+                    let prop = rng.choose(b"xmas") as char;
+                    let comp = rng.choose(b"<>") as char;
+                    format!("{}{}{}:{}", prop, comp, rng.range(1..=4000), Self::action(rng, below))
+                })
+                .collect::<Vec<_>>();
+            rules.push(Self::action(rng, below));
+            out += &format!("{}{{{}}}\n", name, rules.join(","));
+        }
+        out += "\n";
+        for _ in 0..self.parts {
+            let [x, m, a, s] = [(); 4].map(|_| rng.range(1..=4000));
+            out += &format!("{{x={},m={},a={},s={}}}\n", x, m, a, s);
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,507 @@
+// Advent-of-Code 2023
+// Day 19
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::math::Overflow;
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+
+pub mod generate;
+
+pub const DAY: usize = 19;
+
+pub const OPTIONS: &[Opt] = &[];
+
+pub mod data {
+
+    use util::iter::*;
+    use util::parse::{Line, ParseError};
+
+    #[derive(PartialEq, Eq, Clone)]
+    pub enum Action {
+        Accept,
+        Reject,
+        SendToWorkflow(String),
+    }
+    pub use Action::*;
+
+    impl Action {
+        fn from(s: &str) -> Self {
+            match s {
+                "A" => Accept,
+                "R" => Reject,
+                _ => SendToWorkflow(s.to_string()),
+            }
+        }
+    }
+
+    #[derive(PartialEq, Eq, Copy, Clone, Hash)]
+    pub enum Prop { X, M, A, S }
+
+    impl Prop {
+        fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+            match s {
+                "x" => Ok(Prop::X),
+                "m" => Ok(Prop::M),
+                "a" => Ok(Prop::A),
+                "s" => Ok(Prop::S),
+                _ => Err(line.error(s, "bad property")),
+            }
+        }
+    }
+
+    #[derive(PartialEq, Eq, Copy, Clone)]
+    pub enum Comp { Less, Greater }
+
+    impl Comp {
+        fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+            match s {
+                "<" => Ok(Comp::Less),
+                ">" => Ok(Comp::Greater),
+                _ => Err(line.error(s, "bad comparison")),
+            }
+        }
+    }
+
+    // Splits s after its first and second character.
+    fn split_two<'a>(line: &Line, s: &'a str) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+        match (s.get(0..1), s.get(1..2), s.get(2..)) {
+            (Some(s0), Some(s1), Some(s2)) => Ok((s0, s1, s2)),
+            _ => Err(line.error(s, "too short")),
+        }
+    }
+
+    pub struct Condition {
+        pub prop: Prop,
+        pub comp: Comp,
+        pub value: i64,
+    }
+
+    impl Condition {
+        fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+            let (prop, comp, value) = split_two(line, s)?;
+            Ok(Condition {
+                prop: Prop::from(line, prop)?,
+                comp: Comp::from(line, comp)?,
+                value: line.parse(value, "condition value")?,
+            })
+        }
+    }
+
+    pub struct Rule {
+        pub condition: Condition,
+        pub action: Action,
+    }
+
+    impl Rule {
+        fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+            match s.split(':').boxed()[..] {
+                [cond_str, act_str] =>
+                    Ok(Rule{
+                        condition: Condition::from(line, cond_str)?,
+                        action: Action::from(act_str),
+                    }),
+                _ => Err(line.error(s, "bad rule")),
+            }
+        }
+    }
+
+    pub struct Workflow {
+        pub name: String,
+        pub rules: Vec<Rule>,
+        pub catchall: Action,
+    }
+
+    impl Workflow {
+        pub fn from(line: &Line) -> Result<Self, ParseError> {
+            match line.text.split('{').boxed()[..] {
+                [name, rest] => {
+                    let rules = rest.strip_suffix('}')
+                        .ok_or_else(|| line.error(rest, "missing '}'"))?;
+                    match rules.split(',').boxed()[..] {
+                        [ref rule_strings @ .., catchall_string] =>
+                            Ok(Workflow {
+                                name: name.to_string(),
+                                rules: rule_strings.iter()
+                                    .map(|&s| Rule::from(line, s))
+                                    .collect::<Result<_, _>>()?,
+                                catchall: Action::from(catchall_string),
+                            }),
+                        _ => Err(line.error(rules, "bad rules")),
+                    }
+                },
+                _ => Err(line.fail("bad workflow")),
+            }
+        }
+    }
+
+    pub struct WorkflowSuite {
+        workflows: Vec<Workflow>,
+    }
+
+    impl WorkflowSuite {
+        pub fn new(v: Vec<Workflow>) -> Self {
+            WorkflowSuite{ workflows: v }
+        }
+
+        pub fn find(&self, name: &str) -> Option<&Workflow> {
+            self.workflows.iter().find(|&w| w.name == name)
+        }
+
+        // Only used after checking that all workflows exist.
+        pub fn get(&self, name: &str) -> &Workflow {
+            self.find(name).expect("workflow?")
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Data<T>{ pub x: T,  pub m: T, pub a: T, pub s: T }
+
+    impl<T> Data<T> where T: Copy {
+        pub fn get(&self, p: Prop) -> T {
+            match p {
+                Prop::X => self.x,
+                Prop::M => self.m,
+                Prop::A => self.a,
+                Prop::S => self.s,
+            }
+        }
+
+        pub fn update(self, p: Prop, v: T) -> Self {
+            match p {
+                Prop::X => Data{ x: v, ..self },
+                Prop::M => Data{ m: v, ..self },
+                Prop::A => Data{ a: v, ..self },
+                Prop::S => Data{ s: v, ..self },
+            }
+        }
+    }
+
+    impl Data<i64> {
+        fn zero() -> Self {
+            Data{ x: 0, m: 0, a: 0, s: 0 }
+        }
+    
+        fn read_prop(self, line: &Line, s: &str) -> Result<Self, ParseError> {
+            let (prop, eq, value) = split_two(line, s)?;
+            if eq != "=" { return Err(line.error(s, "bad prop value spec")) };
+            Ok(self.update(Prop::from(line, prop)?,
+                           line.parse(value, "data value")?))
+        }
+
+        pub fn from(line: &Line) -> Result<Self, ParseError> {
+            line.text.strip_prefix('{')
+                .and_then(|s| s.strip_suffix('}'))
+                .ok_or_else(|| line.fail("expected {...}"))?
+                .split(',')
+                .try_fold(Self::zero(), |data, s| data.read_prop(line, s))
+        }
+    }
+}
+
+mod part1 {
+    use crate::data::*;
+    use std::collections::HashSet;
+    
+    impl Data<i64> {
+        // Ratings can be anything, so their sum might not fit.
+        pub fn total(&self) -> Option<i64> {
+            self.x.checked_add(self.m)?.checked_add(self.a)?.checked_add(self.s)
+        }
+    }
+
+    impl Condition {
+        pub fn holds_for(&self, data: Data<i64>) -> bool {
+            let prop_val =  data.get(self.prop);
+            match self.comp {
+                Comp::Less => prop_val < self.value,
+                Comp::Greater => prop_val > self.value,
+            }
+        }
+    }
+    
+    impl Rule {
+        fn eval(&self, data: Data<i64>) -> Option<&Action> {
+            self.condition.holds_for(data).then_some(&self.action)
+        }
+    }
+
+    impl Workflow {
+        fn eval(&self, data: Data<i64>) -> &Action {
+            self.rules.iter()
+                .find_map(|rule| rule.eval(data))
+                .unwrap_or(&self.catchall)
+            }
+    }
+
+    impl WorkflowSuite {
+        pub fn accepts(&self, data: Data<i64>) -> bool {
+            let mut cur = "in";
+            let mut seen = HashSet::new();
+            loop {
+                if seen.contains(cur) { panic!("cycle at {}", cur) };
+                seen.insert(cur);
+                match self.get(cur).eval(data) {
+                    Accept => return true,
+                    Reject => return false,
+                    SendToWorkflow(w) => cur = w,
+                }
+            }
+        }
+    }
+}
+
+mod part2 {
+    use crate::data::*;
+    use std::collections::HashSet;
+
+    use util::interval::{HyperRect, Interval};
+
+    // The ranges of all four properties, indexed by Prop.
+    pub type RangeData = HyperRect<4>;
+
+    type Key<'a> = (&'a str, RangeData);
+    type Seen<'a> = HashSet<Key<'a>>;
+
+    pub fn full_range(start: i64, len: i64) -> RangeData {
+        HyperRect([Interval::with_len(start, len); 4])
+    }
+
+    impl Action {
+        fn count<'a>(&'a self, rd: RangeData, workflows: &'a WorkflowSuite,
+                 seen: &mut Seen<'a>) -> i64 {
+            match self {
+                Accept => rd.volume(),
+                Reject => 0,
+                SendToWorkflow(w) =>
+                    workflows.get(w).count(rd, workflows, seen),
+            }
+        }
+    }
+
+    impl Comp {
+        // Splits the interval into the parts for which the comparison
+        // with v holds and for which it does not.
+        fn split_range(self, i: Interval, v: i64)
+                       -> (Option<Interval>, Option<Interval>) {
+            match self {
+                Comp::Less => i.split_at(v),
+                Comp::Greater => {
+                    let (low, high) = i.split_at(v + 1);
+                    (high, low)
+                },
+            }
+        }
+    }
+
+    impl Condition {
+        fn split(&self, rd: &RangeData)
+                 -> (Option<RangeData>, Option<RangeData>) {
+            let &Condition{ prop: p, comp: c, value: v } = self;
+            let (good_range, bad_range) = c.split_range(rd.0[p as usize], v);
+            let good = good_range.map(|g| rd.with(p as usize, g));
+            let bad = bad_range.map(|b| rd.with(p as usize, b));
+            (good, bad)
+        }
+    }
+
+    impl Rule {
+        fn split(&self, rd: &RangeData)
+                 -> (Option<RangeData>, Option<RangeData>) {
+            self.condition.split(rd)
+        }
+    }
+
+    impl Workflow {
+        fn count<'a>(&'a self, rd: RangeData, workflows: &'a WorkflowSuite,
+                 seen: &mut Seen<'a>) -> i64 {
+            let key = (self.name.as_str(), rd);
+            if seen.contains(&key) { panic!("cycle at {}!", self.name) };
+            seen.insert(key);
+            let mut cur = rd;
+            let mut total = 0;
+            for rule in self.rules.iter() {
+                let (good, bad) = rule.split(&cur);
+                if let Some(g) = good {
+                    total += rule.action.count(g, workflows, seen);
+                };
+                if let Some(b) = bad {
+                    cur = b;
+                } else {
+                    return total
+                }
+            }
+            total += self.catchall.count(cur, workflows, seen);
+            total
+        }
+    }
+
+    impl WorkflowSuite {
+        pub fn count(&self, rd: RangeData) -> i64 {
+            self.get("in").count(rd, self, &mut HashSet::new())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn split_range_less() {
+            let i = Interval::closed(1, 4000);
+            assert_eq!(Comp::Less.split_range(i, 1351),
+                       (Some(Interval::closed(1, 1350)), Some(Interval::closed(1351, 4000))));
+            assert_eq!(Comp::Less.split_range(i, 1), (None, Some(i)));
+            assert_eq!(Comp::Less.split_range(i, 4001), (Some(i), None));
+        }
+
+        #[test]
+        fn split_range_greater() {
+            let i = Interval::closed(1, 4000);
+            assert_eq!(Comp::Greater.split_range(i, 2770),
+                       (Some(Interval::closed(2771, 4000)), Some(Interval::closed(1, 2770))));
+            assert_eq!(Comp::Greater.split_range(i, 0), (Some(i), None));
+            assert_eq!(Comp::Greater.split_range(i, 4000), (None, Some(i)));
+        }
+    }
+}
+
+use data::*;
+
+// Names of the workflows that a workflow line refers to, which are
+// the actions (after ':' or on their own) other than A and R.
+fn referenced_workflows(line: &str) -> impl Iterator<Item = &str> {
+    line.split(['{', ',', '}'])
+        .skip(1)
+        .filter_map(|r| r.rsplit(':').next())
+        .filter(|&w| !w.is_empty() && w != "A" && w != "R")
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = (WorkflowSuite, Vec<Data<i64>>);
+    type Answer1 = Result<i64, Overflow>;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        let mut workflows = Vec::new();
+        let mut data_section = false;
+        let mut data = Vec::new();
+        let mut targets = Vec::new();
+        for line in parse::lines(input) {
+            if line.text.is_empty() {
+                data_section = true;
+            } else if !data_section {
+                workflows.push(Workflow::from(&line)?);
+                targets.extend(referenced_workflows(line.text).map(|w| (line, w)));
+            } else {
+                data.push(Data::from(&line)?);
+            }
+        }
+        let suite = WorkflowSuite::new(workflows);
+        if suite.find("in").is_none() {
+            return Err(parse::eof_error(input, "no workflow named 'in'"))
+        }
+        if let Some((line, w)) = targets.iter().find(|(_, w)| suite.find(w).is_none()) {
+            return Err(line.error(w, "unknown workflow"))
+        }
+        Ok((suite, data))
+    }
+
+    fn part1(&self, (suite, data): &Self::Model) -> Result<i64, Overflow> {
+        data.iter()
+            .filter(|&&d| suite.accepts(d))
+            .try_fold(0i64, |sum, d| sum.checked_add(d.total()?))
+            .ok_or(Overflow)
+    }
+
+    // The accepted boxes do not overlap, so their volumes add up to at
+    // most 4000^4, which leaves plenty of room in an i64.
+    fn part2(&self, (suite, _): &Self::Model) -> i64 {
+        suite.count(part2::full_range(1, 4000))
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), Ok(19114));
+        assert_eq!(Solver.part2(&model), 167409079868000);
+    }
+
+    #[test]
+    fn ratings_overflow() {
+        let input = "in{A}\n\n{x=9223372036854775807,m=1,a=0,s=0}\n";
+        assert_eq!(Solver.part1(&Solver.parse(input).unwrap()), Err(Overflow));
+    }
+
+    // Turns accepting into rejecting and vice versa.
+    fn swap_decisions(input: &str) -> String {
+        input.lines()
+            .map(|line| {
+                let line = if line.starts_with('{') {
+                    line.to_string()
+                } else {
+                    line.replace(":A", ":_").replace(":R", ":A").replace(":_", ":R")
+                        .replace(",A}", ",_}").replace(",R}", ",A}").replace(",_}", ",R}")
+                        .replace("{A}", "{_}").replace("{R}", "{A}").replace("{_}", "{R}")
+                };
+                line + "\n"
+            })
+            .collect()
+    }
+
+    // Every combination of ratings is either accepted or rejected.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ workflows: 100, depth: 6, ..Default::default() };
+        for seed in 0..20 {
+            let input = gen.generate(&mut Rng::new(seed));
+            let model = Solver.parse(&input).unwrap();
+            let swapped = Solver.parse(&swap_decisions(&input)).unwrap();
+            assert_eq!(Solver.part2(&model) + Solver.part2(&swapped), 4000i64.pow(4));
+            let all = model.1.iter().map(|d| d.total().unwrap()).sum::<i64>();
+            assert_eq!(Solver.part1(&model).unwrap() + Solver.part1(&swapped).unwrap(), all);
+        }
+    }
+}
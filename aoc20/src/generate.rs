@@ -0,0 +1,86 @@
+// Random inputs: circuits built like the puzzle input.  The broadcaster
+// feeds a number of binary counters made of flip-flops.  Each counter
+// has a conjunction watching the bits that are set in its period, which
+// resets the counter when it gets there and, through an inverter,
+// tells the final conjunction in front of rx.
+
+use std::collections::HashSet;
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("counters", "n", "number of counters (default 4)"),
+    Opt::value("bits", "n", "number of flip-flops per counter (default 12)"),
+];
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator {
+    pub counters: usize,
+    pub bits: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ counters: 4, bits: 12 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            counters: args.value_in("counters", d.counters, 1..=8)?,
+            bits: args.value_in("bits", d.bits, 2..=16)?,
+        })
+    }
+
+    fn name(rng: &mut Rng, used: &mut HashSet<String>) -> String {
+        loop {
+            let len = 2 + rng.index(2);
+            let name = rng.word(len, LETTERS);
+            if used.insert(name.clone()) { return name }
+        }
+    }
+
+    // The period is odd and uses the top bit, so that the counter
+    // counts all the way up from the first flip-flop.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut used = HashSet::from([String::from("rx"), String::from("broadcaster")]);
+        let last = Self::name(rng, &mut used);
+        let mut lines = vec![format!("&{} -> rx", last)];
+        let mut firsts = Vec::new();
+        for _ in 0..self.counters {
+            let flops = (0..self.bits).map(|_| Self::name(rng, &mut used)).collect::<Vec<_>>();
+            let hub = Self::name(rng, &mut used);
+            let inverter = Self::name(rng, &mut used);
+            let period = rng.range(1 << (self.bits - 1)..=(1 << self.bits) - 1) | 1;
+            let mut hub_outs = vec![inverter.clone(), flops[0].clone()];
+            for (i, flop) in flops.iter().enumerate() {
+                let mut outs = Vec::new();
+                if let Some(next) = flops.get(i + 1) {
+                    outs.push(next.clone());
+                }
+                if period & (1 << i) != 0 {
+                    outs.push(hub.clone());
+                } else {
+                    hub_outs.push(flop.clone());
+                }
+                rng.shuffle(&mut outs);
+                lines.push(format!("%{} -> {}", flop, outs.join(", ")));
+            }
+            rng.shuffle(&mut hub_outs);
+            lines.push(format!("&{} -> {}", hub, hub_outs.join(", ")));
+            lines.push(format!("&{} -> {}", inverter, last));
+            firsts.push(flops[0].clone());
+        }
+        lines.push(format!("broadcaster -> {}", firsts.join(", ")));
+        rng.shuffle(&mut lines);
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,51 @@
+// Random inputs: a square garden with the start in the middle and, as
+// in the puzzle input, a clear row and column through the start and a
+// clear border all around.
+
+use util::cli::{Args, Opt, UsageError};
+use util::grid::Grid;
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the garden, odd (default 131)"),
+    Opt::value("rocks", "p", "chance of a rock at each plot (default 0.15)"),
+];
+
+pub struct Generator {
+    pub size: usize,
+    pub rocks: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 131, rocks: 0.15 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let size = args.value_in("size", d.size, 3..)?;
+        if size % 2 == 0 {
+            return Err(UsageError(format!("--size must be odd: '{}'", size)))
+        }
+        Ok(Generator{ size, rocks: args.value_in("rocks", d.rocks, 0.0..=1.0)? })
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let (n, mid) = (self.size, self.size / 2);
+        let mut grid = Grid::filled(n, n, '.');
+        for (r, c) in grid.positions() {
+            let clear = r == mid || c == mid || r == 0 || c == 0 || r == n - 1 || c == n - 1;
+            if !clear && rng.chance(self.rocks) {
+                grid[(r, c)] = '#';
+            }
+        }
+        grid[(mid, mid)] = 'S';
+        grid.to_string()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
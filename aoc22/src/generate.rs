@@ -0,0 +1,72 @@
+// Random inputs: snapshots of bricks falling through the air.  Bricks
+// never overlap, and some of them stand upright.
+
+use std::collections::HashSet;
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("bricks", "n", "number of bricks (default 1400)"),
+    Opt::value("area", "n", "width and depth of the area the bricks fall on (default 10)"),
+    Opt::value("length", "n", "maximum length of a brick (default 5)"),
+    Opt::value("tall", "p", "chance of a brick standing upright (default 0.2)"),
+];
+
+pub struct Generator {
+    pub bricks: usize,
+    pub area: i64,
+    pub length: i64,
+    pub tall: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ bricks: 1400, area: 10, length: 5, tall: 0.2 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let area = args.value_in("area", d.area, 1..)?;
+        Ok(Generator{
+            bricks: args.value_in("bricks", d.bricks, 1..)?,
+            area,
+            length: args.value_in("length", d.length, 1..=area)?,
+            tall: args.value_in("tall", d.tall, 0.0..=1.0)?,
+        })
+    }
+
+    // The two ends of a random brick with its lowest point at z.
+    fn brick(&self, rng: &mut Rng, z: i64) -> [[i64; 3]; 2] {
+        let mut size = [1; 3];
+        let axis = if rng.chance(self.tall) { 2 } else { rng.index(2) };
+        size[axis] = rng.range(1..=self.length);
+        let low = [rng.range(0..=self.area - size[0]), rng.range(0..=self.area - size[1]), z];
+        [low, [0, 1, 2].map(|i| low[i] + size[i] - 1)]
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let height = (self.bricks as i64 * self.length / self.area).max(1);
+        let mut taken = HashSet::new();
+        let mut out = String::new();
+        let mut placed = 0;
+        while placed < self.bricks {
+            let z = rng.range(1..=height);
+            let [a, b] = self.brick(rng, z);
+            let cubes = (a[0]..=b[0])
+                .flat_map(|x| (a[1]..=b[1]).flat_map(move |y| (a[2]..=b[2]).map(move |z| (x, y, z))))
+                .collect::<Vec<_>>();
+            if cubes.iter().any(|c| taken.contains(c)) { continue }
+            taken.extend(cubes);
+            out += &format!("{},{},{}~{},{},{}\n", a[0], a[1], a[2], b[0], b[1], b[2]);
+            placed += 1;
+        }
+        out
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,315 @@
+// Advent-of-Code 2023
+// Day 22
+// Author: Matthias Blume
+
+use std::collections::HashSet;
+
+use util::animate::{self, Frame, Simulation};
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::grid::Grid;
+use util::interval::Interval;
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::Color;
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 22;
+
+pub const OPTIONS: &[Opt] = &[animate::ANIMATE, animate::FPS, animate::FRAMES];
+
+struct Point {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl Point {
+    fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+        match s.split(',').boxed()[..] {
+            [xs, ys, zs] =>
+                Ok(Point{
+                    x: line.parse(xs, "x coordinate")?,
+                    y: line.parse(ys, "y coordinate")?,
+                    z: line.parse(zs, "z coordinate")?,
+                }),
+            _ => Err(line.error(s, "bad point")),
+        }
+    }
+}
+
+// The closed interval between the two coordinates.
+fn extent(a: i64, b: i64) -> Interval {
+    Interval::closed(a.min(b), a.max(b))
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Brick {
+    z: Interval,  // (derived) lexicographic ordering prefers z
+    x: Interval,
+    y: Interval,
+}
+
+impl Brick {
+    fn from(line: Line) -> Result<Self, ParseError> {
+        match line.text.split('~').boxed()[..] {
+            [s1, s2] => Ok(Self::from_points(Point::from(&line, s1)?, Point::from(&line, s2)?)),
+            _ => Err(line.fail("bad brick")),
+        }
+    }
+
+    fn from_points(p1: Point, p2: Point) -> Self {
+        Brick{
+            x: extent(p1.x, p2.x),
+            y: extent(p1.y, p2.y),
+            z: extent(p1.z, p2.z),
+        }
+    }
+
+    fn can_collide_with(&self, other: &Self) -> bool {
+        self.x.overlaps(&other.x) &&
+            self.y.overlaps(&other.y)
+    }
+
+    fn supports(&self, other: &Self) -> bool {
+        self.can_collide_with(other) && self.z.end == other.z.start
+    }
+}
+
+struct Stacking {
+    bricks: Vec<Brick>
+}
+
+type IndexSet = HashSet<usize>;
+
+impl Stacking {
+    fn len(&self) -> usize {
+        self.bricks.len()
+    }
+
+    fn insert(&mut self, b: Brick) {
+        let z = (0..self.len())
+            .filter_map(|i| self.bricks[i]
+                        .can_collide_with(&b)
+                        .then_some(self.bricks[i].z.end))
+            .max()
+            .unwrap_or(1);
+        self.bricks.push(
+            Brick{ z: b.z.shift(z - b.z.start), ..b });
+    }
+
+    fn from_bricks(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort();
+        let mut s = Stacking{ bricks: Vec::new() };
+        bricks.into_iter().for_each(|b| s.insert(b));
+        s
+    }
+
+    fn supported(&self, n: usize) -> IndexSet {
+        let b = &self.bricks[n];
+        (n+1..self.len()).filter(|&i| b.supports(&self.bricks[i])).collect()
+    }
+
+    fn all_supported(&self) -> Vec<IndexSet> {
+        (0..self.len()).map(|i| self.supported(i)).collect()
+    }
+}
+
+// The bricks settling one at a time, lowest first, as seen from the
+// front: x across and z up, like in the puzzle description.
+struct Settling {
+    falling: Vec<Brick>,
+    stacking: Stacking,
+}
+
+const BRICK_COLORS: [Color; 4] = [Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW];
+
+impl Settling {
+    fn new(mut bricks: Vec<Brick>) -> Self {
+        bricks.sort();
+        bricks.reverse();
+        Settling{ falling: bricks, stacking: Stacking{ bricks: Vec::new() } }
+    }
+}
+
+impl Simulation for Settling {
+    // Bricks further back are hidden behind those in front.  Settled
+    // bricks are labeled in the order they came to rest.
+    fn frame(&self) -> Frame {
+        let bricks = || self.falling.iter().chain(&self.stacking.bricks);
+        let top = bricks().map(|b| b.z.high()).max().unwrap_or(0).max(1) as usize;
+        let width = bricks().map(|b| b.x.end).max().unwrap_or(0) as usize;
+        let mut cells = Grid::filled(top + 1, width, ('.', Color(90, 90, 90)));
+        let mut depth = Grid::filled(top + 1, width, i64::MAX);
+        for c in 0..width {
+            cells[(top, c)] = ('-', Color::GRAY);
+        }
+        let settled = self.stacking.bricks.iter().enumerate()
+            .map(|(i, b)| (b, ((b'A' + (i % 26) as u8) as char, BRICK_COLORS[i % BRICK_COLORS.len()])));
+        let falling = self.falling.iter().map(|b| (b, ('#', Color::GRAY)));
+        for (b, cell) in settled.chain(falling) {
+            for x in b.x.low()..=b.x.high() {
+                for z in b.z.low().max(1)..=b.z.high() {
+                    let p = (top - z as usize, x as usize);
+                    if b.y.low() < depth[p] {
+                        depth[p] = b.y.low();
+                        cells[p] = cell;
+                    }
+                }
+            }
+        }
+        let caption = format!("{} bricks settled, {} falling", self.stacking.len(), self.falling.len());
+        Frame{ cells, caption }
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(b) = self.falling.pop() else { return false };
+        self.stacking.insert(b);
+        true
+    }
+}
+
+pub struct SupportInfo {
+    sz: usize,
+    supported: Vec<IndexSet>,
+    num_supporters: Vec<usize>,
+}
+
+impl SupportInfo {
+    fn from(stacking: &Stacking) -> Self {
+        let sz = stacking.len();
+        let supported = stacking.all_supported();
+        let mut num_supporters = vec![0; sz];
+        supported.iter()
+            .for_each(|s| s.iter()
+                      .for_each(|&i| num_supporters[i] += 1));
+        SupportInfo{ sz, supported, num_supporters }
+    }
+
+    fn is_removable(&self, n: usize) -> bool {
+        self.supported[n].iter().all(|&i| self.num_supporters[i] != 1)
+    }
+
+    fn num_toppled(&self, n: usize) -> usize {
+        let mut remaining_support = self.num_supporters.clone();
+        let mut count = 0;
+        let mut stack = Vec::new();
+        stack.push(n);
+        while let Some(i) = stack.pop() {
+            for &j in &self.supported[i] {
+                remaining_support[j] -= 1;
+                if remaining_support[j] == 0 {
+                    count += 1;
+                    stack.push(j);
+                }
+            }
+        }
+        count
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = SupportInfo;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<SupportInfo, ParseError> {
+        let bricks: Vec<Brick> =
+            parse::lines(input).map(Brick::from).collect::<Result<_, _>>()?;
+        Ok(SupportInfo::from(&Stacking::from_bricks(bricks)))
+    }
+
+    fn part1(&self, sinfo: &SupportInfo) -> usize {
+        (0..sinfo.sz).filter(|&n| sinfo.is_removable(n)).count()
+    }
+
+    fn part2(&self, sinfo: &SupportInfo) -> usize {
+        (0..sinfo.sz).map(|n| sinfo.num_toppled(n)).sum()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    animate::play(args, || {
+        let bricks = parse::lines(input).map(Brick::from).collect::<Result<_, _>>()?;
+        Ok(Settling::new(bricks))
+    })?;
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 5);
+        assert_eq!(Solver.part2(&model), 7);
+    }
+
+    // Settling one brick at a time ends up with the same stack.
+    #[test]
+    fn example_settling() {
+        let bricks = || parse::lines(EXAMPLE).map(Brick::from).collect::<Result<Vec<_>, _>>().unwrap();
+        let mut settling = Settling::new(bricks());
+        while settling.step() {}
+        assert!(settling.stacking.bricks == Stacking::from_bricks(bricks()).bricks);
+        let frame = settling.frame();
+        let rows = frame.cells.rows().map(|r| r.iter().map(|c| c.0).collect::<String>()).collect::<Vec<_>>();
+        assert_eq!(rows, [".G.", ".G.", "FFF", "D.E", "BBB", ".A.", "---"]);
+    }
+
+    // Takes out each brick in turn, lets the others settle again, and
+    // counts the ones that fall.
+    fn falling(input: &str) -> Vec<usize> {
+        let bricks = parse::lines(input).map(Brick::from).collect::<Result<Vec<_>, _>>().unwrap();
+        let settled = Stacking::from_bricks(bricks);
+        (0..settled.len())
+            .map(|n| {
+                let mut others = settled.bricks.iter().enumerate()
+                    .filter(|&(i, _)| i != n)
+                    .map(|(_, b)| Brick{ ..*b })
+                    .collect::<Vec<_>>();
+                others.sort();
+                let before = others.iter().map(|b| b.z).collect::<Vec<_>>();
+                let after = Stacking::from_bricks(others);
+                after.bricks.iter().zip(before).filter(|(b, z)| b.z != *z).count()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ bricks: 40, area: 4, length: 3, tall: 0.3 };
+        for seed in 0..10 {
+            let input = gen.generate(&mut Rng::new(seed));
+            let sinfo = Solver.parse(&input).unwrap();
+            let falling = falling(&input);
+            assert_eq!(Solver.part1(&sinfo), falling.iter().filter(|&&n| n == 0).count());
+            assert_eq!(Solver.part2(&sinfo), falling.iter().sum());
+        }
+    }
+}
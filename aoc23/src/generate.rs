@@ -0,0 +1,119 @@
+// Random inputs: a maze of forest paths from the top row to the bottom
+// row, with a few extra openings that make for loops, and slopes that
+// point away from the start.
+
+use std::collections::VecDeque;
+
+use util::cli::{Args, Opt, UsageError};
+use util::direction::Direction;
+use util::grid::{Grid, Pos};
+use util::random::Rng;
+
+use Direction::*;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("size", "n", "height and width of the map, odd (default 141)"),
+    Opt::value("loops", "p", "chance of each remaining wall being opened (default 0.003)"),
+    Opt::value("slopes", "p", "chance of each opening having a slope (default 0.5)"),
+];
+
+pub struct Generator {
+    pub size: usize,
+    pub loops: f64,
+    pub slopes: f64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ size: 141, loops: 0.003, slopes: 0.5 }
+    }
+}
+
+fn slope(d: Direction) -> char {
+    match d { North => '^', East => '>', South => 'v', West => '<' }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let size = args.value_in("size", d.size, 3..)?;
+        if size % 2 == 0 {
+            return Err(UsageError(format!("--size must be odd: '{}'", size)))
+        }
+        Ok(Generator{
+            size,
+            loops: args.value_in("loops", d.loops, 0.0..=1.0)?,
+            slopes: args.value_in("slopes", d.slopes, 0.0..=1.0)?,
+        })
+    }
+
+    // Rooms are at odd positions, and the walls between neighboring
+    // rooms get opened: first along a random spanning tree, then some
+    // more.  Slopes go on openings, pointing away from the start.
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let n = self.size;
+        let mut grid = Grid::filled(n, n, '#');
+        let (start, end) = ((0, 1), (n - 1, n - 2));
+        grid[start] = '.';
+        grid[end] = '.';
+        grid[(1, 1)] = '.';
+        let mut openings = Vec::new();
+        let mut stack = vec![(1, 1)];
+        while let Some(&room) = stack.last() {
+            let next = Direction::ALL.iter()
+                .filter_map(|&d| Some((grid.go(grid.go(room, d)?, d)?, d)))
+                .filter(|&(r, _)| r.0 % 2 == 1 && r.1 % 2 == 1 && grid[r] == '#')
+                .collect::<Vec<_>>();
+            if next.is_empty() {
+                stack.pop();
+                continue
+            }
+            let (r, d) = rng.choose(&next);
+            let wall = grid.go(room, d).expect("wall inside");
+            grid[wall] = '.';
+            grid[r] = '.';
+            openings.push(wall);
+            stack.push(r);
+        }
+        for r in (1..n - 1).step_by(2) {
+            for c in (1..n - 1).step_by(2) {
+                for wall in [(r, c + 1), (r + 1, c)] {
+                    if wall.0 < n - 1 && wall.1 < n - 1 && grid[wall] == '#' && rng.chance(self.loops) {
+                        grid[wall] = '.';
+                        openings.push(wall);
+                    }
+                }
+            }
+        }
+        let distance = distances(&grid, start);
+        for wall in openings {
+            if !rng.chance(self.slopes) { continue }
+            let downhill = Direction::ALL.into_iter()
+                .find(|&d| grid.go(wall, d).is_some_and(|p| grid[p] != '#' && distance[p] > distance[wall]));
+            if let Some(d) = downhill {
+                grid[wall] = slope(d);
+            }
+        }
+        grid.to_string()
+    }
+}
+
+// Shortest distances from the start, along paths.
+fn distances(grid: &Grid<char>, start: Pos) -> Grid<usize> {
+    let mut distance = grid.map(|_| usize::MAX);
+    distance[start] = 0;
+    let mut queue = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        for q in grid.neighbors4(p).collect::<Vec<_>>() {
+            if grid[q] != '#' && distance[q] == usize::MAX {
+                distance[q] = distance[p] + 1;
+                queue.push_back(q);
+            }
+        }
+    }
+    distance
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,249 @@
+// Advent-of-Code 2023
+// Day 23
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, Part, RunResult};
+use util::direction::Direction;
+use util::grid::Grid;
+use util::parse::{self, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::render::{self, Color, Picture};
+
+pub mod generate;
+
+pub const DAY: usize = 23;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("render", "file", "draw a longest hike (of part 2 with --part 2) to the given .svg or .ppm file"),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Spot {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+use Spot::*;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    WithSlopes,
+    WithoutSlopes,
+}
+use Mode::*;
+
+fn read_spot(c: char, mode: Mode) -> Option<Spot> {
+    match (c, mode) {
+        ('.', _) => Some(Path),
+        ('#', _) => Some(Forest),
+        ('^' | 'v' | '>' | '<', WithoutSlopes) => Some(Path),
+        ('^' | 'v' | '>' | '<', WithSlopes) => Direction::from_char(c).map(Slope),
+        _ => None,
+    }
+}
+
+struct Field {
+    height: i64,
+    spots: Grid<Spot>,
+}
+
+type Pos = (i64, i64);
+type PosSet = Grid<bool>;
+
+impl Field {
+    fn from(s: &str, mode: Mode) -> Result<Self, ParseError> {
+        let spots = Grid::parse(s, |c| read_spot(c, mode), "spot")?;
+        if !spots.row(0).contains(&Path) {
+            let msg = "no starting position";
+            return Err(parse::lines(s).next().map_or_else(|| parse::eof_error(s, msg), |l| l.fail(msg)));
+        }
+        Ok(Field{ height: spots.height() as i64, spots })
+    }
+
+    fn start(&self) -> Pos {
+        let j = self.spots.row(0).iter()
+            .position(|&f| f == Path)
+            .expect("starting position");
+        (0, j as i64)
+    }
+    
+    // Tries all hikes from p on, keeping the longest one that gets to
+    // the bottom row.  Path is the hike so far.
+    fn all_paths(&self, p: Pos,
+                 path: &mut Vec<Pos>,
+                 visited: &mut PosSet,
+                 longest: &mut Vec<Pos>) {
+        let Some(q) = self.spots.index_of(p) else { return };
+        if visited[q] { return };
+        let f = self.spots[q];
+        if f == Forest { return };
+        path.push(p);
+        if p.0 == self.height - 1 {
+            if path.len() > longest.len() {
+                longest.clone_from(path);
+            }
+            path.pop();
+            return
+        }
+        visited[q] = true;
+        let next =
+            match f {
+                Forest => vec![],
+                Path => Direction::ALL.map(|d| d.step(p)).to_vec(),
+                Slope(d) => vec![d.step(p)],
+            };
+        next.into_iter()
+            .for_each(|np| self.all_paths(np, path, visited, longest));
+        visited[q] = false;
+        path.pop();
+    }
+
+    // The positions along a longest hike, start and end included.
+    fn longest_hike(&self) -> Vec<Pos> {
+        let mut longest = Vec::new();
+        let mut visited = self.spots.map(|_| false);
+        self.all_paths(self.start(), &mut Vec::new(), &mut visited, &mut longest);
+        longest
+    }
+
+    fn longest_path(&self) -> usize {
+        self.longest_hike().len().saturating_sub(1)
+    }
+
+    // The map with a longest hike on it.
+    fn picture(&self) -> Picture {
+        let mut picture = Picture::from(&self.spots, |&f| match f {
+            Path => Color::WHITE,
+            Forest => Color::GREEN,
+            Slope(_) => Color::GRAY,
+        });
+        picture.path(self.longest_hike().into_iter().map(|(i, j)| (i as usize, j as usize)), Color::RED);
+        picture
+    }
+}
+
+// The map read with slopes (part 1) and without them (part 2).
+pub struct Map(Field, Field);
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Map, ParseError> {
+        Ok(Map(Field::from(input, WithSlopes)?, Field::from(input, WithoutSlopes)?))
+    }
+
+    fn part1(&self, Map(with_slopes, _): &Map) -> usize {
+        with_slopes.longest_path()
+    }
+
+    fn part2(&self, Map(_, without_slopes): &Map) -> usize {
+        without_slopes.longest_path()
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    let answers = puzzle::solve(&Solver, args, input)?;
+    if let Some(file) = render::target(args)? {
+        let mode = if args.part == Some(Part::Two) { WithoutSlopes } else { WithSlopes };
+        Field::from(input, mode)?.picture().write(&file)?;
+    }
+    Ok(answers)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.#####.#.#v#.#.#.###
+#...#...#.#.>.#.#.#.###
+#.###.#.#.#.#v#.#.#.###
+#.....#...#...#.#.#...#
+#####################.#
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 94);
+    }
+
+    // A hike goes one step at a time and never visits a spot twice.
+    #[test]
+    fn example_hike() {
+        let field = Field::from(EXAMPLE, WithSlopes).unwrap();
+        let hike = field.longest_hike();
+        assert_eq!(hike.len(), 95);
+        assert_eq!(hike[0], field.start());
+        assert!(hike.windows(2).all(|w| (w[0].0 - w[1].0).abs() + (w[0].1 - w[1].1).abs() == 1));
+        let mut spots = hike.clone();
+        spots.sort();
+        spots.dedup();
+        assert_eq!(spots.len(), hike.len());
+    }
+
+    fn shortest(field: &Field) -> usize {
+        let mut distance = field.spots.map(|_| usize::MAX);
+        let mut frontier = vec![field.spots.index_of(field.start()).unwrap()];
+        for d in 0.. {
+            assert!(!frontier.is_empty(), "no way through");
+            if frontier.iter().any(|p| p.0 == field.spots.height() - 1) { return d }
+            frontier.iter().for_each(|&p| distance[p] = d);
+            frontier = frontier.iter()
+                .flat_map(|&p| field.spots.neighbors4(p).collect::<Vec<_>>())
+                .filter(|&q| field.spots[q] != Forest && distance[q] == usize::MAX)
+                .collect();
+        }
+        unreachable!()
+    }
+
+    // In a maze without loops there is just the one way through.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ size: 21, loops: 0.0, slopes: 0.5 };
+        for seed in 0..20 {
+            let input = gen.generate(&mut Rng::new(seed));
+            let map = Solver.parse(&input).unwrap();
+            let way = shortest(&map.1);
+            assert_eq!(Solver.part1(&map), way);
+            assert_eq!(Solver.part2(&map), way);
+        }
+        let gen = generate::Generator{ size: 11, loops: 0.2, slopes: 0.5 };
+        for seed in 0..20 {
+            let map = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            assert!(Solver.part1(&map) <= Solver.part2(&map));
+        }
+    }
+}
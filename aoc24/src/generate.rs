@@ -0,0 +1,77 @@
+// Random inputs: hailstones that a single rock, thrown from some point
+// with some velocity, hits one after the other.
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("hailstones", "n", "number of hailstones (default 300)"),
+    Opt::value("area", "n", "extent of the area of the rock's starting point (default 4e14)"),
+    Opt::value("speed", "n", "maximum speed along each axis (default 300)"),
+];
+
+pub type Vector = [i64; 3];
+
+pub struct Generator {
+    pub hailstones: usize,
+    pub area: i64,
+    pub speed: i64,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ hailstones: 300, area: 400_000_000_000_000, speed: 300 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        let area = args.value_or("area", d.area as f64)? as i64;
+        let speed = args.value_in("speed", d.speed, 1..)?;
+        if area < 4 * speed {
+            return Err(UsageError(format!("--area must be at least 4 times --speed: '{}'", area)))
+        }
+        Ok(Generator{ hailstones: args.value_in("hailstones", d.hailstones, 4..)?, area, speed })
+    }
+
+    // Velocity components are never zero.
+    fn velocity(&self, rng: &mut Rng) -> Vector {
+        [(); 3].map(|_| rng.range(1..=self.speed) * if rng.chance(0.5) { -1 } else { 1 })
+    }
+
+    // The rock's starting position and velocity.
+    pub fn rock(&self, rng: &mut Rng) -> (Vector, Vector) {
+        ([(); 3].map(|_| rng.range(self.area / 2..=self.area)), self.velocity(rng))
+    }
+
+    // Each hailstone is where the rock meets it at a time of its own,
+    // and comes from where it was at time zero.
+    pub fn hail(&self, rng: &mut Rng, (position, velocity): (Vector, Vector)) -> String {
+        let max_time = self.area / (4 * self.speed);
+        let mut times = Vec::new();
+        while times.len() < self.hailstones {
+            let t = rng.range(1..=max_time);
+            if !times.contains(&t) { times.push(t) }
+        }
+        times.iter()
+            .map(|&t| {
+                let v = loop {
+                    let v = self.velocity(rng);
+                    if v != velocity { break v }
+                };
+                let p = [0, 1, 2].map(|i| position[i] + t * (velocity[i] - v[i]));
+                format!("{}, {}, {} @ {}, {}, {}\n", p[0], p[1], p[2], v[0], v[1], v[2])
+            })
+            .collect()
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let rock = self.rock(rng);
+        self.hail(rng, rock)
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
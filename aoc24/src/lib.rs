@@ -0,0 +1,278 @@
+// Advent-of-Code 2023
+// Day 24
+// Author: Matthias Blume
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult, UsageError};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 24;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("low", "x", "lower bound of the test area (part 1, default 2e14)"),
+    Opt::value("high", "x", "upper bound of the test area (part 1, default 4e14)"),
+];
+
+#[derive(Copy, Clone, Debug)]
+struct Vector {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Vector {
+    fn from(line: &Line, s: &str) -> Result<Self, ParseError> {
+        match s.split(',').map(str::trim).boxed()[..] {
+            [xs, ys, zs] => Ok(Vector{
+                x: line.parse(xs, "x")?,
+                y: line.parse(ys, "y")?,
+                z: line.parse(zs, "z")?,
+            }),
+            _ => Err(line.error(s, "bad vector")),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct State {
+    position: Vector,
+    velocity: Vector,
+}
+
+impl State {
+    fn from(line: Line) -> Result<Self, ParseError> {
+        match line.text.split('@').boxed()[..] {
+            [p, v] =>
+                Ok(State {
+                    position: Vector::from(&line, p)?,
+                    velocity: Vector::from(&line, v)?,
+                }),
+            _ => Err(line.fail("bad state")),
+        }
+    }
+}
+
+// Find x-y coordinates where the projections of the two vectors
+// into the x-y plane intersect.
+// Determine whether all of the following are true:
+//  - The intersection exists.
+//  - It lies in the future (t >= 0) for both vectors.
+//  - Both x and y coordinates of the intersection lie within
+//    the interval low..=high.
+fn has_xy_collision(
+    State { position: Vector{ x: x1, y: y1, .. },
+            velocity: Vector{ x: vx1, y: vy1, .. } }: State,
+    State{ position: Vector{ x: x2, y: y2, .. },
+           velocity: Vector{ x: vx2, y: vy2, .. } }: State,
+    low: f64, high: f64) -> bool {
+    let d = vy1 * vx2 - vx1 * vy2;
+    if d == 0.0 { return false }  // parallel
+    let t = ((x1 - x2) * vy2 - (y1 - y2) * vx2) / d;
+    if t < 0.0 { return false }   // in the past for one
+    let t2 = ((x1 - x2) + t * vx1) / vx2;
+    if t2 < 0.0 { return false }  // .. or for the other
+    let x = x1 + t * vx1;
+    let y = y1 + t * vy1;
+    x >= low && x <= high && y >= low && y <= high
+}
+
+// Solve set of linear equations using Gaussian elimination.
+#[allow(clippy::needless_range_loop)]
+fn gauss(mut m: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = m.len();
+    assert_eq!(n, b.len());
+    assert_eq!(n, m[0].len());
+    // Bring m into row echelon form (and adjust b accordingly):
+    for h in 0..n {
+        // Find best pivot.
+        let mut i_max = h;
+        for i in h+1..n {
+            if m[i][h].abs() > m[i_max][h].abs() { i_max = i }
+        }
+        if m[i_max][h] == 0.0 { return None }  // determinant 0
+        if i_max != h {
+            m.swap(h, i_max);
+            b.swap(h, i_max);
+        }
+        let pvt = m[h][h];
+        for i in h+1..n {
+            let f = m[i][h] / pvt;
+            m[i][h] = 0.0;
+            for j in h+1..n {
+                m[i][j] -= m[h][j] * f;
+            }
+            b[i] -= b[h] * f;
+        }
+    }
+    // Row echelon form achieved! Now substitute back:
+    for h in (0..n).rev() {
+        for i in h+1..n {
+            b[h] -= m[h][i] * b[i];
+        }
+        b[h] /= m[h][h];
+    }
+    Some(b)
+}
+
+pub struct Solver {
+    low: f64,
+    high: f64,
+}
+
+impl Solver {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        Ok(Solver{
+            low: args.value_or("low", 200_000_000_000_000.0)?,
+            high: args.value_or("high", 400_000_000_000_000.0)?,
+        })
+    }
+}
+
+impl Puzzle for Solver {
+    type Model = Vec<State>;
+    type Answer1 = usize;
+    type Answer2 = Result<f64, &'static str>;
+
+    fn parse(&self, input: &str) -> Result<Vec<State>, ParseError> {
+        parse::lines(input).map(State::from).collect()
+    }
+
+    fn part1(&self, states: &Vec<State>) -> usize {
+        (0..states.len())
+            .map(|i| (i+1..states.len())
+                 .filter(|&j| has_xy_collision(states[i], states[j], self.low, self.high))
+                 .count())
+            .sum()
+    }
+
+    fn part2(&self, states: &Vec<State>) -> Result<f64, &'static str> {
+        // Grab the first four inputs and construct system of linear equations.
+        //
+        // Notes: Any four should do. Three inputs already determine
+        // the solution, but the resulting system of equations is
+        // non-linear.  When using four inputs we can remove the non-linearity
+        // by treating non-linear (mixed) terms as if they were independent
+        // variables. These turn out to be linear, so we can just solve for
+        // them along with the rest.
+
+        // Matrix rows alternate between y- and z-types, one of each per input.
+        let yrow = | i | { let s: State = states[i];
+                           vec![1.0, 0.0,
+                                -s.velocity.y, s.velocity.x, 0.0,
+                                s.position.y, -s.position.x, 0.0] };
+        let zrow = | i | { let s: State = states[i];
+                           vec![0.0, 1.0,
+                                -s.velocity.z, 0.0, s.velocity.x,
+                                s.position.z, 0.0, -s.position.x] };
+
+        // Likewise, right-hand sides alternate between y- and z-types.
+        let yrhs = | i | { let s: State = states[i];
+                           s.velocity.x * s.position.y
+                           - s.position.x * s.velocity.y };
+        let zrhs = | i | { let s: State = states[i];
+                           s.velocity.x * s.position.z
+                           - s.position.x * s.velocity.z };
+        
+        // Coefficient matrix m:
+        let m = (0..4).flat_map(|i| [yrow(i), zrow(i)]).collect();
+
+        // Right-hand side vector b:
+        let b = (0..4).flat_map(|i| [yrhs(i), zrhs(i)]).collect();
+
+        if let Some(solution) = gauss(m, b) {
+            // Slots 0 and 1 contain solutions for the above mentioned
+            // non-linear terms.
+            let x = solution[2];
+            let y = solution[3];
+            let z = solution[4];
+            // Velocity components are in slots 5, 6, and 7.
+            Ok(x + y + z)
+        } else {
+            Err("no solution")
+        }
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver::from(args)?, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver::from(args)?, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn example() {
+        let solver = Solver{ low: 7.0, high: 27.0 };
+        let model = solver.parse(EXAMPLE).unwrap();
+        assert_eq!(solver.part1(&model), 2);
+        assert_eq!(solver.part2(&model), Ok(47.0));
+    }
+
+    fn assert_close(x: &[f64], expected: &[f64]) {
+        assert_eq!(x.len(), expected.len());
+        for (a, b) in x.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-9, "{:?} != {:?}", x, expected);
+        }
+    }
+
+    #[test]
+    fn gauss_solves_regular_system() {
+        let m = vec![vec![2.0, 1.0, -1.0],
+                     vec![-3.0, -1.0, 2.0],
+                     vec![-2.0, 1.0, 2.0]];
+        let x = gauss(m, vec![8.0, -11.0, -3.0]).unwrap();
+        assert_close(&x, &[2.0, 3.0, -1.0]);
+    }
+
+    #[test]
+    fn gauss_swaps_rows_for_zero_pivot() {
+        let m = vec![vec![0.0, 1.0],
+                     vec![1.0, 0.0]];
+        let x = gauss(m, vec![2.0, 3.0]).unwrap();
+        assert_close(&x, &[3.0, 2.0]);
+    }
+
+    #[test]
+    fn gauss_rejects_singular_system() {
+        let m = vec![vec![1.0, 2.0],
+                     vec![2.0, 4.0]];
+        assert_eq!(gauss(m, vec![1.0, 2.0]), None);
+    }
+
+    // The rock found is the one that the hail was made for.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ hailstones: 10, area: 100_000, speed: 50 };
+        let solver = Solver{ low: 0.0, high: 100_000.0 };
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let rock = gen.rock(&mut rng);
+            let states = solver.parse(&gen.hail(&mut rng, rock)).unwrap();
+            let expected = rock.0.iter().sum::<i64>() as f64;
+            let found = solver.part2(&states).unwrap();
+            assert!((found - expected).abs() < 1e-3, "{} != {}", found, expected);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Random inputs: two well-connected groups of components, with exactly
+// three wires between them.
+
+use std::collections::{BTreeMap, HashSet};
+
+use util::cli::{Args, Opt, UsageError};
+use util::random::Rng;
+
+pub const OPTIONS: &[Opt] = &[
+    Opt::value("components", "n", "number of components (default 1500)"),
+    Opt::value("extra", "n", "extra wires per component inside a group (default 1)"),
+];
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator {
+    pub components: usize,
+    pub extra: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator{ components: 1500, extra: 1 }
+    }
+}
+
+impl Generator {
+    pub fn from(args: &Args) -> Result<Self, UsageError> {
+        let d = Self::default();
+        Ok(Generator{
+            components: args.value_in("components", d.components, 10..=10000)?,
+            extra: args.value_in("extra", d.extra, 0..)?,
+        })
+    }
+
+    // Wires within a group of at least five: each component is wired
+    // to the next two in a ring, which takes cutting four wires to
+    // split, and then some more at random.
+    fn group(&self, rng: &mut Rng, names: &[String], wires: &mut HashSet<(usize, usize)>, offset: usize) {
+        let n = names.len();
+        for i in 0..n {
+            for j in [i + 1, i + 2] {
+                wires.insert((offset + i, offset + j % n));
+            }
+            for _ in 0..self.extra {
+                let j = rng.index(n);
+                if j != i {
+                    wires.insert((offset + i, offset + j));
+                }
+            }
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng) -> String {
+        let mut used = HashSet::new();
+        let names = (0..self.components)
+            .map(|_| loop {
+                let name = rng.word(3, LETTERS);
+                if used.insert(name.clone()) { break name }
+            })
+            .collect::<Vec<_>>();
+        let n1 = rng.range(5..=self.components as i64 - 5) as usize;
+        let mut wires = HashSet::new();
+        self.group(rng, &names[..n1], &mut wires, 0);
+        self.group(rng, &names[n1..], &mut wires, n1);
+        while wires.iter().filter(|&&(a, b)| (a < n1) != (b < n1)).count() < 3 {
+            wires.insert((rng.index(n1), n1 + rng.index(self.components - n1)));
+        }
+        // Each wire is listed with one of its ends, and each pair only once.
+        let mut listed = HashSet::new();
+        let mut lines: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+        let mut wires = wires.into_iter().collect::<Vec<_>>();
+        wires.sort();
+        for (a, b) in wires {
+            if !listed.insert((a.min(b), a.max(b))) { continue }
+            let (from, to) = if rng.chance(0.5) { (a, b) } else { (b, a) };
+            lines.entry(from).or_default().push(&names[to]);
+        }
+        let mut lines = lines.into_iter()
+            .map(|(from, to)| format!("{}: {}\n", names[from], to.join(" ")))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut lines);
+        lines.concat()
+    }
+}
+
+pub fn generate(args: &Args, rng: &mut Rng) -> Result<String, UsageError> {
+    Ok(Generator::from(args)?.generate(rng))
+}
@@ -0,0 +1,184 @@
+// Advent-of-Code 2023
+// Day 25
+// Author: Matthias Blume
+
+use std::collections::{HashMap,HashSet};
+use pathfinding::directed::edmonds_karp::edmonds_karp_sparse;
+
+use util::bench::{self, BenchResult};
+use util::cli::{Args, Opt, RunResult};
+use util::parse::{self, Line, ParseError};
+use util::puzzle::{self, Puzzle, SolveResult};
+use util::iter::*;
+
+pub mod generate;
+
+pub const DAY: usize = 25;
+
+pub const OPTIONS: &[Opt] = &[];
+
+type Node<'a> = &'a str;
+type NodeSet<'a> = HashSet<Node<'a>>;
+type NodeVector<'a> = Vec<Node<'a>>;
+type Edge<'a> = (Node<'a>, Node<'a>);
+type EdgeSet<'a> = HashSet<Edge<'a>>;
+
+struct Graph<'a> {
+    nodes: NodeVector<'a>,
+    edges: EdgeSet<'a>,
+    successors: HashMap<Node<'a>, NodeSet<'a>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new() -> Self {
+        Graph{
+            nodes: Vec::new(),
+            edges: HashSet::new(),
+            successors: HashMap::new(),
+        }
+    }
+
+    fn insert_node(&mut self, n: Node<'a>) {
+        if !self.nodes.contains(&n) {
+            self.nodes.push(n);
+        }
+    }
+    
+    fn insert_edge(&mut self, e: Edge<'a>) {
+        self.edges.insert(e);
+        self.successors.entry(e.0).or_default().insert(e.1);
+    }
+    
+    fn visit_all(&self, n: Node<'a>,
+                 visited: &mut NodeSet<'a>,
+                 ignored: &NodeSet<'a>) {
+        if ignored.contains(&n) || visited.contains(&n) { return }
+        visited.insert(n);
+        if let Some(s) = self.successors.get(&n) {
+            for &m in s {
+                self.visit_all(m, visited, ignored);
+            }
+        }
+    }
+
+    fn num_reachable(&self, n: Node<'a>, ignored: &NodeSet<'a>) -> usize {
+        let mut visited = HashSet::new();
+        self.visit_all(n, &mut visited, ignored);
+        visited.len()
+    }
+    
+    fn insert_connections(&mut self, x: Node<'a>, ys: &'a [String]) {
+        for y in ys {
+            self.insert_node(x);
+            self.insert_node(y);
+            self.insert_edge((x, y));
+            self.insert_edge((y, x));
+        }
+    }
+
+    fn ek(&self, n: usize) -> Option<usize> {
+        let v0 = self.nodes[0];
+        let vn = self.nodes[n];
+        match edmonds_karp_sparse(&self.nodes, &v0, &vn,
+                                  self.edges.iter().cloned().map(|e| (e, 1))) {
+            (_, 3, mc) => {
+                let ignored_from_0 =
+                    mc.iter().map(|((_, y), _)| *y).collect();
+                let ignored_from_n =
+                    mc.iter().map(|((x, _), _)| *x).collect();
+                let num_0 = self.num_reachable(v0, &ignored_from_0);
+                let num_n = self.num_reachable(vn, &ignored_from_n);
+                Some(num_0 * num_n)
+            },
+            _ => None
+        }
+    }
+
+    fn find_ek(&self) -> usize {
+        (1..self.nodes.len()).find_map(|i| self.ek(i)).unwrap_or(0)
+    }
+}
+
+// Each line names a component and the components it is connected to.
+fn read_line(line: Line) -> Result<(String, Vec<String>), ParseError> {
+    match line.text.split(':').boxed()[..] {
+        [left, right] if !left.trim().is_empty() =>
+            Ok((left.trim().to_string(), right.split_whitespace().map(String::from).collect())),
+        _ => Err(line.fail("bad line")),
+    }
+}
+
+pub struct Solver;
+
+impl Puzzle for Solver {
+    type Model = Vec<(String, Vec<String>)>;
+    type Answer1 = usize;
+    type Answer2 = &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse::lines(input).map(read_line).collect()
+    }
+
+    fn part1(&self, connections: &Self::Model) -> usize {
+        let mut g = Graph::new();
+        for (x, ys) in connections {
+            g.insert_connections(x, ys);
+        }
+        g.find_ek()
+    }
+
+    fn part2(&self, _: &Self::Model) -> &'static str {
+        "day 25 has no part 2"
+    }
+}
+
+pub fn solve(args: &Args, input: &str) -> SolveResult {
+    puzzle::solve(&Solver, args, input)
+}
+
+pub fn bench(args: &Args, input: &str, runs: usize) -> BenchResult {
+    bench::measure(&Solver, args, input, runs)
+}
+
+pub fn run(args: &Args) -> RunResult {
+    puzzle::run(DAY, solve, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use util::random::Rng;
+
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn example() {
+        let model = Solver.parse(EXAMPLE).unwrap();
+        assert_eq!(Solver.part1(&model), 54);
+    }
+
+    // The answer splits the components into the two groups.
+    #[test]
+    fn generated_inputs() {
+        let gen = generate::Generator{ components: 60, extra: 1 };
+        for seed in 0..20 {
+            let model = Solver.parse(&gen.generate(&mut Rng::new(seed))).unwrap();
+            let product = Solver.part1(&model);
+            assert!((5..=55).any(|a| a * (60 - a) == product), "{}", product);
+        }
+    }
+}
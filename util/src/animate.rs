@@ -0,0 +1,105 @@
+// Watching simulations run: days that get to their answer step by step
+// expose the steps as a Simulation, which --animate plays back in the
+// terminal, one frame per step, using ANSI escapes for colors and for
+// drawing each frame over the previous one.
+
+use std::error::Error;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::{Args, Opt, RunResult, UsageError};
+use crate::grid::Grid;
+use crate::render::Color;
+
+// The options of days that can be animated.
+pub const ANIMATE: Opt = Opt::flag("animate", "show the simulation in the terminal before the answers");
+pub const FPS: Opt = Opt::value("fps", "n", "frames per second when animating (default 10)");
+pub const FRAMES: Opt = Opt::value("frames", "n", "most frames to show when animating (default 1000)");
+
+// What the terminal shows at one point: a character in some color for
+// every cell, and a line of text below.
+pub struct Frame {
+    pub cells: Grid<(char, Color)>,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn from<T>(grid: &Grid<T>, cell: impl FnMut(&T) -> (char, Color), caption: String) -> Self {
+        Frame{ cells: grid.map(cell), caption }
+    }
+
+    // The frame with escapes for the colors, switching only when the
+    // color changes.
+    pub fn to_ansi(&self) -> String {
+        let mut s = String::new();
+        for row in self.cells.rows() {
+            let mut current = None;
+            for &(c, color) in row {
+                if current != Some(color) {
+                    let Color(r, g, b) = color;
+                    s.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b));
+                    current = Some(color);
+                }
+                s.push(c);
+            }
+            s.push_str("\x1b[0m\n");
+        }
+        s.push_str(&self.caption);
+        s.push_str("\x1b[K\n");
+        s
+    }
+}
+
+pub trait Simulation {
+    // The current state.
+    fn frame(&self) -> Frame;
+    // Advances by one step, or tells that the simulation is over.
+    fn step(&mut self) -> bool;
+}
+
+pub struct Animation {
+    pub fps: f64,
+    pub frames: usize,
+}
+
+impl Animation {
+    // The animation requested by the options, if any.
+    pub fn from(args: &Args) -> Result<Option<Self>, UsageError> {
+        if !args.flag(ANIMATE.name) {
+            return Ok(None)
+        }
+        let fps: f64 = args.value_or(FPS.name, 10.0)?;
+        if !(fps > 0.0 && fps.is_finite()) {
+            return Err(UsageError(format!("--{} must be positive", FPS.name)))
+        }
+        Ok(Some(Animation{ fps, frames: args.value_or(FRAMES.name, 1000)? }))
+    }
+
+    // Shows the frames of the simulation until it is over or the number
+    // of frames is used up.
+    pub fn play(&self, simulation: &mut impl Simulation, out: &mut impl Write) -> io::Result<()> {
+        let pause = Duration::from_secs_f64(1.0 / self.fps);
+        write!(out, "\x1b[2J")?;
+        for n in 0..self.frames {
+            if n > 0 {
+                if !simulation.step() { break }
+                thread::sleep(pause);
+            }
+            write!(out, "\x1b[H{}", simulation.frame().to_ansi())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+// Plays the animation requested by the options, if any, on standard
+// error, so that standard output has nothing but the answers, whatever
+// their format.
+pub fn play<S: Simulation>(args: &Args, simulation: impl FnOnce() -> Result<S, Box<dyn Error>>)
+                           -> RunResult {
+    if let Some(animation) = Animation::from(args)? {
+        animation.play(&mut simulation()?, &mut io::stderr().lock())?;
+    }
+    Ok(())
+}
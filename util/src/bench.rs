@@ -0,0 +1,77 @@
+// Benchmarking: runs the phases of a puzzle (parsing and the two parts)
+// over and over and collects wall-clock times.
+//
+// Parsing is timed on its own; the parts are timed on a model that was
+// parsed beforehand.
+
+use std::error::Error;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::cli::{Args, Part};
+use crate::puzzle::Puzzle;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // Statistics for a non-empty list of samples.
+    pub fn from(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats{ runs: n, min: samples[0], median, max: samples[n - 1] }
+    }
+}
+
+pub type Timings = Vec<(Phase, Stats)>;
+pub type BenchResult = Result<Timings, Box<dyn Error>>;
+
+fn sample<T>(runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    Stats::from((0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    black_box(f());
+                    start.elapsed()
+                })
+                .collect())
+}
+
+// Times parsing and the requested part(s), each the given number of times.
+pub fn measure<P: Puzzle>(puzzle: &P, args: &Args, input: &str, runs: usize) -> BenchResult {
+    let model = puzzle.parse(input)?;
+    let mut timings = vec![(Phase::Parse, sample(runs, || puzzle.parse(input)))];
+    if args.wants(Part::One) {
+        timings.push((Phase::Solve(Part::One), sample(runs, || puzzle.part1(&model))));
+    }
+    if args.wants(Part::Two) {
+        timings.push((Phase::Solve(Part::Two), sample(runs, || puzzle.part2(&model))));
+    }
+    Ok(timings)
+}
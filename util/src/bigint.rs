@@ -0,0 +1,172 @@
+// Unsigned integers of any size, for answers that outgrow 64 bits.
+//
+// Days whose answers can get that large compute them in u64 with
+// overflow checks by default, and in BigUint when given --big.  Only what
+// these days need is here: addition, multiplication, and printing.
+
+use std::fmt;
+
+use crate::cli::Opt;
+use crate::math::{Count, Overflow};
+
+pub const BIG: Opt = Opt::flag("big", "compute the answers with big integers, which do not overflow");
+
+// Digits in base 2^32, least significant first, without leading zeros
+// (so zero has none).
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct BigUint(Vec<u32>);
+
+impl BigUint {
+    pub fn from(n: u64) -> Self {
+        let mut b = BigUint(vec![n as u32, (n >> 32) as u32]);
+        b.trim();
+        b
+    }
+
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    pub fn add(&self, other: &BigUint) -> BigUint {
+        let mut digits = Vec::with_capacity(self.0.len().max(other.0.len()) + 1);
+        let mut carry = 0;
+        for i in 0..self.0.len().max(other.0.len()) {
+            let sum = *self.0.get(i).unwrap_or(&0) as u64 + *other.0.get(i).unwrap_or(&0) as u64 + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        digits.push(carry as u32);
+        let mut b = BigUint(digits);
+        b.trim();
+        b
+    }
+
+    pub fn mul(&self, other: &BigUint) -> BigUint {
+        let mut digits = vec![0u32; self.0.len() + other.0.len()];
+        for (i, &x) in self.0.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y) in other.0.iter().enumerate() {
+                let t = x as u64 * y as u64 + digits[i + j] as u64 + carry;
+                digits[i + j] = t as u32;
+                carry = t >> 32;
+            }
+            digits[i + other.0.len()] = carry as u32;
+        }
+        let mut b = BigUint(digits);
+        b.trim();
+        b
+    }
+
+    // The value, if it fits.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.0.len() > 4 { return None }
+        let n = self.0.iter().rev().fold(0u128, |n, &d| n << 32 | d as u128);
+        i128::try_from(n).ok()
+    }
+
+    // Divides by d in place and returns the remainder.
+    fn div_rem(&mut self, d: u32) -> u32 {
+        let mut rem = 0u64;
+        for digit in self.0.iter_mut().rev() {
+            let cur = rem << 32 | *digit as u64;
+            *digit = (cur / d as u64) as u32;
+            rem = cur % d as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+}
+
+impl fmt::Display for BigUint {
+    // Nine decimal digits at a time, most significant chunk first.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.0.is_empty() {
+            chunks.push(n.div_rem(CHUNK));
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter().rev().try_for_each(|c| write!(f, "{:09}", c))
+            },
+        }
+    }
+}
+
+impl Count for BigUint {
+    fn from_u64(n: u64) -> Self {
+        BigUint::from(n)
+    }
+
+    fn plus(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self.add(other))
+    }
+
+    fn times(&self, other: &Self) -> Result<Self, Overflow> {
+        Ok(self.mul(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    fn from_u128(n: u128) -> BigUint {
+        BigUint::from((n >> 64) as u64).mul(&BigUint::from(1 << 32).mul(&BigUint::from(1 << 32)))
+            .add(&BigUint::from(n as u64))
+    }
+
+    // Values near the limb boundaries, and random ones of all sizes.
+    fn samples() -> Vec<u128> {
+        let mut samples = vec![0, 1, u32::MAX as u128, 1 << 32, u64::MAX as u128, 1 << 64,
+                               (1 << 96) - 1, 1 << 96, u128::MAX >> 1, u128::MAX];
+        let mut rng = Rng::new(128);
+        for _ in 0..200 {
+            let bits = rng.index(128) as u32;
+            samples.push((rng.next_u64() as u128) << 64 | rng.next_u64() as u128 >> bits);
+            samples.push(rng.next_u64() as u128 >> bits.min(63));
+        }
+        samples
+    }
+
+    #[test]
+    fn agrees_with_u128() {
+        let samples = samples();
+        for &a in &samples {
+            assert_eq!(from_u128(a).to_string(), a.to_string());
+            assert_eq!(from_u128(a).to_i128(), i128::try_from(a).ok());
+            for &b in &samples[..50] {
+                if let Some(sum) = a.checked_add(b) {
+                    assert_eq!(from_u128(a).add(&from_u128(b)), from_u128(sum), "{} + {}", a, b);
+                }
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(from_u128(a).mul(&from_u128(b)), from_u128(product), "{} * {}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_u128() {
+        assert_eq!(BigUint::from(0), BigUint::default());
+        assert_eq!(BigUint::from(0).to_string(), "0");
+        assert_eq!(BigUint::from(0).mul(&from_u128(u128::MAX)), BigUint::default());
+        let max = from_u128(u128::MAX);
+        assert_eq!(max.add(&BigUint::from(1)).to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(max.add(&BigUint::from(1)).to_i128(), None);
+        assert_eq!(max.mul(&max).to_string(),
+                   "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+        let billion = BigUint::from(1_000_000_000);
+        let mut n = BigUint::from(1);
+        for k in 1..=5 {
+            n = n.mul(&billion);
+            assert_eq!(n.to_string(), format!("1{}", "0".repeat(9 * k)));
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// Command-line handling shared by all days.
+//
+// Every day accepts arguments of the same shape:
+//
+//   <file>... [--part 1|2] [--format text|json] [--<option> <value> | --<flag>]...
+//
+// A file name of "-" stands for standard input.
+//
+// Day-specific options are declared up front as a list of Opt values,
+// so that unknown or malformed options are rejected before any work is
+// done, and so that the usage message can list them.
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::process;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+// The two halves of a day's puzzle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part { One, Two }
+
+impl FromStr for Part {
+    type Err = UsageError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(UsageError(format!("bad part '{}', expected 1 or 2", s))),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// How answers are printed: as text for people, or as JSON records (one
+// per line) for scripts.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = UsageError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(UsageError(format!("bad format '{}', expected text or json", s))),
+        }
+    }
+}
+
+// A day-specific named option.  Options with a placeholder take a value
+// ("--stretch 10" or "--stretch=10"), the others are plain flags.
+#[derive(Clone, Copy)]
+pub struct Opt {
+    pub name: &'static str,
+    pub placeholder: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl Opt {
+    pub const fn flag(name: &'static str, help: &'static str) -> Self {
+        Opt{ name, placeholder: None, help }
+    }
+
+    pub const fn value(name: &'static str, placeholder: &'static str,
+                       help: &'static str) -> Self {
+        Opt{ name, placeholder: Some(placeholder), help }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+// What running a day produces.  Usage errors are reported together with
+// the usage message, other errors on their own.
+pub type RunResult = Result<(), Box<dyn Error>>;
+
+pub struct Args {
+    pub files: Vec<String>,
+    pub part: Option<Part>,
+    pub format: Format,
+    values: HashMap<&'static str, String>,
+    flags: HashSet<&'static str>,
+}
+
+impl Args {
+    // Parses the arguments following the program name (or subcommand),
+    // accepting only the given day-specific options besides --part and
+    // --format.
+    pub fn parse<I>(args: I, opts: &[Opt]) -> Result<Self, UsageError>
+    where I: IntoIterator<Item = String> {
+        let mut parsed = Args{
+            files: Vec::new(),
+            part: None,
+            format: Format::Text,
+            values: HashMap::new(),
+            flags: HashSet::new(),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                parsed.files.push(arg);
+                continue
+            };
+            let (name, inline_value) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (option, None),
+            };
+            let needs_value = |v: Option<String>| v.ok_or_else(
+                || UsageError(format!("--{} needs a value", name)));
+            if name == "part" {
+                parsed.part = Some(needs_value(inline_value.or_else(|| args.next()))?.parse()?);
+                continue
+            }
+            if name == "format" {
+                parsed.format = needs_value(inline_value.or_else(|| args.next()))?.parse()?;
+                continue
+            }
+            let opt = opts.iter()
+                .find(|o| o.name == name)
+                .ok_or_else(|| UsageError(format!("unknown option --{}", name)))?;
+            match (opt.placeholder, inline_value) {
+                (Some(_), v) => {
+                    let value = needs_value(v.or_else(|| args.next()))?;
+                    parsed.values.insert(opt.name, value);
+                },
+                (None, None) => { parsed.flags.insert(opt.name); },
+                (None, Some(_)) =>
+                    return Err(UsageError(format!("--{} does not take a value", name))),
+            }
+        }
+        Ok(parsed)
+    }
+
+    // Is the given part requested?  Without --part, both parts are.
+    pub fn wants(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    // The input files, of which there must be at least one.
+    pub fn files(&self) -> Result<&[String], UsageError> {
+        if self.files.is_empty() {
+            return Err(UsageError(String::from("no input file name")))
+        }
+        Ok(&self.files)
+    }
+
+    // The (single) input file.
+    pub fn file(&self) -> Result<&str, UsageError> {
+        match &self.files[..] {
+            [file] => Ok(file),
+            [] => Err(UsageError(String::from("no input file name"))),
+            _ => Err(UsageError(String::from("too many input files"))),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    pub fn value<T: FromStr>(&self, name: &str) -> Result<Option<T>, UsageError> {
+        self.values.get(name)
+            .map(|v| v.parse().map_err(
+                |_| UsageError(format!("bad value for --{}: '{}'", name, v))))
+            .transpose()
+    }
+
+    pub fn value_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, UsageError> {
+        Ok(self.value(name)?.unwrap_or(default))
+    }
+
+    // Like value_or, also requiring the value to lie in the given range.
+    pub fn value_in<T, R>(&self, name: &str, default: T, range: R) -> Result<T, UsageError>
+    where T: FromStr + PartialOrd, R: RangeBounds<T> {
+        let value = self.value_or(name, default)?;
+        if !range.contains(&value) {
+            let given = self.values.get(name).map_or("", String::as_str);
+            return Err(UsageError(format!("value for --{} out of range: '{}'", name, given)))
+        }
+        Ok(value)
+    }
+
+    pub fn required<T: FromStr>(&self, name: &str) -> Result<T, UsageError> {
+        self.value(name)?.ok_or_else(|| UsageError(format!("missing --{}", name)))
+    }
+}
+
+// Reads the named input file, or standard input for "-".
+pub fn read_input(file: &str) -> Result<String, Box<dyn Error>> {
+    if file == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input)
+    }
+    fs::read_to_string(file).map_err(|e| format!("{}: {}", file, e).into())
+}
+
+// Opens the named input file, or standard input for "-", for reading
+// as it streams.
+pub fn open_input(file: &str) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    if file == "-" {
+        return Ok(Box::new(io::stdin().lock()))
+    }
+    let f = fs::File::open(file).map_err(|e| format!("{}: {}", file, e))?;
+    Ok(Box::new(BufReader::new(f)))
+}
+
+// Usage message listing --part and --format as well as the given
+// day-specific options.
+pub fn usage(program: &str, opts: &[Opt]) -> String {
+    let mut s = format!("usage: {} <file>... [--part 1|2] [--format text|json]", program);
+    for opt in opts {
+        match opt.placeholder {
+            Some(p) => s.push_str(&format!(" [--{} <{}>]", opt.name, p)),
+            None => s.push_str(&format!(" [--{}]", opt.name)),
+        }
+    }
+    s.push('\n');
+    s.push_str("  <file>...  input files, '-' for standard input\n");
+    s.push_str("  --part 1|2  solve only the given part (default: both)\n");
+    s.push_str("  --format text|json  print answers as text or as JSON records (default: text)\n");
+    for opt in opts {
+        s.push_str(&format!("  --{}  {}\n", opt.name, opt.help));
+    }
+    s
+}
+
+// Reports the outcome of running a day and exits on failure.
+pub fn report(program: &str, opts: &[Opt], result: RunResult) {
+    if let Err(e) = result {
+        eprintln!("{}: {}", program, e);
+        if e.is::<UsageError>() {
+            eprint!("{}", usage(program, opts));
+            process::exit(2);
+        }
+        process::exit(1);
+    }
+}
+
+// Entry point for the individual day binaries.
+pub fn main(opts: &[Opt], run: fn(&Args) -> RunResult) {
+    let mut args = env::args();
+    let program = args.next().unwrap_or_else(|| String::from("aoc"));
+    let result = Args::parse(args, opts).map_err(Box::from).and_then(|a| run(&a));
+    report(&program, opts, result);
+}
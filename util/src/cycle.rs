@@ -0,0 +1,128 @@
+// Cycle detection for iterated state machines.
+//
+// Starting from some state and applying a step function over and over
+// eventually repeats a state if there are only finitely many.  From then
+// on the sequence is periodic: after a prefix of some length, a cycle of
+// some length repeats forever.  Knowing both lets us jump to the state
+// after a huge number of steps.
+//
+// States are compared through a key, which can be a cheaper summary of
+// the state as long as equal keys mean equal futures.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cycle {
+    pub prefix: u64,
+    pub length: u64,
+}
+
+impl Cycle {
+    // The first step at which the state is the same as at step n.
+    pub fn reduce(&self, n: u64) -> u64 {
+        if n < self.prefix { n } else { self.prefix + (n - self.prefix) % self.length }
+    }
+}
+
+// The state after n steps, and the cycle if it was found on the way.
+pub struct Outcome<S> {
+    pub cycle: Option<Cycle>,
+    pub state: S,
+}
+
+// Remembers the key of every state seen, so it takes as many steps as
+// the prefix and one round of the cycle, but memory for all of them.
+// Stops early if step n comes first.
+pub fn with_history<S, K: Eq + Hash>(start: S, n: u64,
+                                     mut step: impl FnMut(S) -> S,
+                                     mut key: impl FnMut(&S) -> K) -> Outcome<S> {
+    let mut history = HashMap::new();
+    let mut state = start;
+    for i in 0..n {
+        if let Some(&prev_i) = history.get(&key(&state)) {
+            let cycle = Cycle{ prefix: prev_i, length: i - prev_i };
+            for _ in 0..(n - i) % cycle.length {
+                state = step(state);
+            }
+            return Outcome{ cycle: Some(cycle), state };
+        }
+        history.insert(key(&state), i);
+        state = step(state);
+    }
+    Outcome{ cycle: None, state }
+}
+
+// Brent's algorithm: keeps only two states at a time, in exchange for
+// taking a few more steps.  There must be a cycle, otherwise this does
+// not terminate.
+pub fn brent<S: Clone, K: Eq>(start: S, n: u64,
+                              mut step: impl FnMut(S) -> S,
+                              mut key: impl FnMut(&S) -> K) -> Outcome<S> {
+    // Find the cycle length: the hare runs ahead in stretches of
+    // doubling length, and the tortoise waits at the start of each.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start.clone());
+    while key(&tortoise) != key(&hare) {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(hare);
+        length += 1;
+    }
+
+    // Find the prefix length: with the hare a cycle length ahead, the
+    // two meet where the cycle begins.
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..length {
+        hare = step(hare);
+    }
+    let mut prefix = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        prefix += 1;
+    }
+
+    let cycle = Cycle{ prefix, length };
+    let (mut state, steps) =
+        if n < prefix { (start, n) } else { (tortoise, (n - prefix) % length) };
+    for _ in 0..steps {
+        state = step(state);
+    }
+    Outcome{ cycle: Some(cycle), state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // x -> x^2 + c mod m, a classic with a prefix before its cycle.
+    fn step(m: u64, c: u64) -> impl Fn(u64) -> u64 {
+        move |x| (x * x + c) % m
+    }
+
+    #[test]
+    fn brent_agrees_with_history() {
+        for (m, c, start) in [(1, 0, 0), (10, 1, 3), (97, 5, 2), (1000, 7, 11), (65537, 3, 1)] {
+            let history = with_history(start, u64::MAX, step(m, c), |&x| x).cycle.unwrap();
+            for n in [0, 1, 2, 5, 50, 1000, 123456789] {
+                let expected = with_history(start, n, step(m, c), |&x| x);
+                let outcome = brent(start, n, step(m, c), |&x| x);
+                assert_eq!(outcome.state, expected.state, "m {} c {} n {}", m, c, n);
+                assert_eq!(outcome.cycle, Some(history));
+            }
+        }
+    }
+
+    #[test]
+    fn cycle_reduce() {
+        let cycle = Cycle{ prefix: 3, length: 4 };
+        assert_eq!([0, 2, 3, 6, 7, 11, 103].map(|n| cycle.reduce(n)), [0, 2, 3, 6, 3, 3, 3]);
+    }
+}
@@ -0,0 +1,195 @@
+// Directions on a map, as used by the grid-based days.
+//
+// Rows grow downwards (south) and columns grow to the right (east), so
+// deltas are (row, column) pairs matching util::grid positions.  Days
+// that think in screen terms can import the same directions under the
+// names Up, Down, Left and Right from the screen module.
+
+use std::str::FromStr;
+
+use crate::grid::SignedPos;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+use Direction::*;
+
+pub mod screen {
+    pub use super::Direction::{North as Up, South as Down, West as Left, East as Right};
+}
+
+// The two axes along which one can move.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Direction {
+    // Clockwise, starting at North.
+    pub const ALL: [Direction; 4] = [North, East, South, West];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            North => West,
+            West => South,
+            South => East,
+            East => North,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            North => South,
+            South => North,
+            East => West,
+            West => East,
+        }
+    }
+
+    pub fn axis(self) -> Axis {
+        match self {
+            North | South => Axis::Vertical,
+            East | West => Axis::Horizontal,
+        }
+    }
+
+    // Change of (row, column) when taking one step.
+    pub fn delta(self) -> SignedPos {
+        match self {
+            North => (-1, 0),
+            South => (1, 0),
+            West => (0, -1),
+            East => (0, 1),
+        }
+    }
+
+    // The position n steps away.
+    pub fn step_n(self, (r, c): SignedPos, n: i64) -> SignedPos {
+        let (dr, dc) = self.delta();
+        (r + n * dr, c + n * dc)
+    }
+
+    pub fn step(self, p: SignedPos) -> SignedPos {
+        self.step_n(p, 1)
+    }
+
+    // Accepts U/D/L/R, arrows (^v<>) and compass letters (N/S/E/W).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' | 'N' => Some(North),
+            'D' | 'v' | 'S' => Some(South),
+            'L' | '<' | 'W' => Some(West),
+            'R' | '>' | 'E' => Some(East),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError;
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c).ok_or(ParseDirectionError),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+// Directions including the diagonal ones.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    // Clockwise, starting at N.
+    pub const ALL: [Direction8; 8] = {
+        use Direction8::*;
+        [N, NE, E, SE, S, SW, W, NW]
+    };
+
+    pub fn delta(self) -> SignedPos {
+        use Direction8::*;
+        match self {
+            N => (-1, 0),
+            NE => (-1, 1),
+            E => (0, 1),
+            SE => (1, 1),
+            S => (1, 0),
+            SW => (1, -1),
+            W => (0, -1),
+            NW => (-1, -1),
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        match d {
+            North => Direction8::N,
+            East => Direction8::E,
+            South => Direction8::S,
+            West => Direction8::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.reverse().reverse(), d);
+            assert_eq!(d.turn_left().turn_left(), d.reverse());
+            assert_ne!(d.turn_left().axis(), d.axis());
+            assert_eq!(d.step_n(d.reverse().step_n((5, 7), 3), 3), (5, 7));
+        }
+        assert_eq!(North.turn_right(), East);
+        assert_eq!("^".parse(), Ok(North));
+        assert_eq!("W".parse(), Ok(West));
+        assert_eq!("".parse::<Direction>(), Err(ParseDirectionError));
+        assert_eq!("UD".parse::<Direction>(), Err(ParseDirectionError));
+    }
+
+    #[test]
+    fn eight_directions() {
+        let deltas: Vec<_> = Direction8::ALL.iter().map(|d| d.delta()).collect();
+        // Each offset around the origin occurs exactly once, going clockwise.
+        assert_eq!(deltas, [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)]);
+        for d in Direction8::ALL {
+            let ((r, c), (rr, rc)) = (d.delta(), d.reverse().delta());
+            assert_eq!((r + rr, c + rc), (0, 0), "{:?}", d);
+        }
+        for d in Direction::ALL {
+            assert_eq!(Direction8::from(d).delta(), d.delta());
+            assert_eq!(Direction8::from(d.reverse()), Direction8::from(d).reverse());
+        }
+    }
+}
@@ -0,0 +1,260 @@
+// Two-dimensional grids of cells, as read from the character maps that
+// many days use as input.
+//
+// Cells are stored row by row.  Positions are (row, column) pairs of
+// unsigned indices.  Code that walks around the grid often finds it
+// easier to work with signed coordinates; those get turned into
+// positions either with a bounds check (index_of) or by wrapping around
+// at the edges, as if the grid were repeated infinitely (wrap).
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::direction::{Direction, Direction8};
+use crate::parse::{self, Line, ParseError};
+
+pub type Pos = (usize, usize);
+pub type SignedPos = (i64, i64);
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // A grid from its cells in row order.
+    pub fn new(height: usize, width: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), height * width, "grid cells do not fit dimensions");
+        Grid{ height, width, cells }
+    }
+
+    pub fn filled(height: usize, width: usize, value: T) -> Self where T: Clone {
+        Grid::new(height, width, vec![value; height * width])
+    }
+
+    // Reads the given lines, converting each character by the given
+    // function.  Characters that do not convert are reported as a bad
+    // <what>.  All rows must have the same length.
+    pub fn from_lines<'a>(lines: impl Iterator<Item = Line<'a>>,
+                          mut convert: impl FnMut(char) -> Option<T>,
+                          what: &str) -> Result<Self, ParseError> {
+        let mut height = 0;
+        let mut width = 0;
+        let mut cells = Vec::new();
+        for line in lines {
+            let before = cells.len();
+            for (i, c) in line.text.chars().enumerate() {
+                cells.push(convert(c).ok_or_else(|| line.error_at(i, c, &format!("bad {}", what)))?);
+            }
+            let len = cells.len() - before;
+            if height == 0 {
+                width = len;
+            } else if len != width {
+                return Err(line.fail("row of wrong length"));
+            }
+            height += 1;
+        }
+        Ok(Grid{ height, width, cells })
+    }
+
+    // Like from_lines, reading the entire input, which must not be empty.
+    pub fn parse(input: &str, convert: impl FnMut(char) -> Option<T>,
+                 what: &str) -> Result<Self, ParseError> {
+        let grid = Self::from_lines(parse::lines(input), convert, what)?;
+        if grid.width == 0 {
+            return Err(parse::eof_error(input, "empty map"));
+        }
+        Ok(grid)
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, (r, c): Pos) -> Option<&T> {
+        (r < self.height && c < self.width).then(|| &self.cells[r * self.width + c])
+    }
+
+    pub fn get_mut(&mut self, (r, c): Pos) -> Option<&mut T> {
+        (r < self.height && c < self.width).then(|| &mut self.cells[r * self.width + c])
+    }
+
+    // The position for the given signed coordinates, if they lie within
+    // the grid.
+    pub fn index_of(&self, (r, c): SignedPos) -> Option<Pos> {
+        let r = usize::try_from(r).ok().filter(|&r| r < self.height)?;
+        let c = usize::try_from(c).ok().filter(|&c| c < self.width)?;
+        Some((r, c))
+    }
+
+    // The position for the given signed coordinates on an infinite
+    // repetition of the grid.
+    pub fn wrap(&self, (r, c): SignedPos) -> Pos {
+        (r.rem_euclid(self.height as i64) as usize,
+         c.rem_euclid(self.width as i64) as usize)
+    }
+
+    // The position one step by the given offset away, unless that
+    // leaves the grid.
+    pub fn step(&self, (r, c): Pos, (dr, dc): SignedPos) -> Option<Pos> {
+        self.index_of((r as i64 + dr, c as i64 + dc))
+    }
+
+    // Like step, moving in the given direction.
+    pub fn go(&self, pos: Pos, d: Direction) -> Option<Pos> {
+        self.step(pos, d.delta())
+    }
+
+    // Positions directly north, east, south and west.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL.into_iter().filter_map(move |d| self.go(pos, d))
+    }
+
+    // Positions around the given one, including diagonally adjacent ones.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL.into_iter().filter_map(move |d| self.step(pos, d.delta()))
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        self.cells[c..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|c| self.column(c))
+    }
+
+    // The grid mirrored along its main diagonal: rows become columns.
+    pub fn transposed(&self) -> Self where T: Clone {
+        Grid{
+            height: self.width,
+            width: self.height,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    // All cells together with their positions, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // The first position (in row order) whose cell satisfies the predicate.
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find_map(|(pos, x)| pred(x).then_some(pos))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid{ height: self.height, width: self.width, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+    fn index(&self, (r, c): Pos) -> &T {
+        assert!(c < self.width, "column {} out of bounds", c);
+        &self.cells[r * self.width + c]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (r, c): Pos) -> &mut T {
+        assert!(c < self.width, "column {} out of bounds", c);
+        &mut self.cells[r * self.width + c]
+    }
+}
+
+// Prints one line per row.  For cells that display as the character
+// they were read from, this gives back the original map.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for x in row {
+                write!(f, "{}", x)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+#..
+.#.
+..#
+.#.
+";
+
+    fn map() -> Grid<char> {
+        Grid::parse(MAP, Some, "cell").unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = map();
+        assert_eq!((grid.height(), grid.width()), (4, 3));
+        assert_eq!(grid.to_string(), MAP);
+        assert_eq!(grid.position(|&c| c == '#'), Some((0, 0)));
+        assert_eq!(grid.column(1).collect::<String>(), ".#.#");
+        assert_eq!(grid.transposed().row(1), &['.', '#', '.', '#']);
+        assert!(Grid::parse("##\n#\n", Some, "cell").is_err());
+        assert!(Grid::parse("", Some, "cell").is_err());
+        assert!(Grid::parse("#x\n", |c| (c == '#').then_some(c), "cell").is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = map();
+        let around = |pos, eight: bool| {
+            let mut v: Vec<Pos> =
+                if eight { grid.neighbors8(pos).collect() } else { grid.neighbors4(pos).collect() };
+            v.sort();
+            v
+        };
+        assert_eq!(around((1, 1), true),
+                   [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        assert_eq!(around((1, 1), false), [(0, 1), (1, 0), (1, 2), (2, 1)]);
+        assert_eq!(around((0, 0), true), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(around((3, 2), true), [(2, 1), (2, 2), (3, 1)]);
+        assert_eq!(around((3, 0), false), [(2, 0), (3, 1)]);
+        // Every neighbor relation goes both ways.
+        for pos in grid.positions() {
+            for n in grid.neighbors8(pos) {
+                assert!(grid.neighbors8(n).any(|p| p == pos), "{:?} {:?}", pos, n);
+            }
+        }
+    }
+
+    #[test]
+    fn coordinates() {
+        let grid = map();
+        assert_eq!(grid.index_of((3, 2)), Some((3, 2)));
+        assert_eq!((grid.index_of((-1, 0)), grid.index_of((0, 3)), grid.index_of((4, 0))),
+                   (None, None, None));
+        assert_eq!(grid.wrap((-1, -1)), (3, 2));
+        assert_eq!(grid.wrap((9, 7)), (1, 1));
+        assert_eq!(grid.go((0, 0), Direction::North), None);
+        assert_eq!(grid.go((0, 0), Direction::South), Some((1, 0)));
+    }
+}
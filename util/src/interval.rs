@@ -0,0 +1,297 @@
+// Integer intervals, sets of them, and boxes built from them.
+//
+// An Interval is stored half-open, as start..end.  Closed intervals
+// low..=high are just another way of creating and looking at the same
+// thing, which keeps the arithmetic in one place.  Empty intervals
+// exist (start >= end), but operations that might produce one return
+// an Option instead.
+
+use std::cmp::{max, min};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    // The half-open interval start..end.
+    pub fn new(start: i64, end: i64) -> Self {
+        Interval{ start, end }
+    }
+
+    // The closed interval low..=high.
+    pub fn closed(low: i64, high: i64) -> Self {
+        Interval{ start: low, end: high + 1 }
+    }
+
+    pub fn with_len(start: i64, len: i64) -> Self {
+        Interval{ start, end: start + len }
+    }
+
+    // start..end unless that would be empty.
+    pub fn non_empty(start: i64, end: i64) -> Option<Self> {
+        (start < end).then_some(Interval{ start, end })
+    }
+
+    // Lowest and highest element, for looking at it as a closed interval.
+    pub fn low(&self) -> i64 {
+        self.start
+    }
+
+    pub fn high(&self) -> i64 {
+        self.end - 1
+    }
+
+    pub fn len(&self) -> i64 {
+        max(0, self.end - self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::non_empty(max(self.start, other.start), min(self.end, other.end))
+    }
+
+    // The part of self that lies to the left of other.
+    pub fn left_of(&self, other: &Self) -> Option<Self> {
+        Self::non_empty(self.start, min(self.end, other.start))
+    }
+
+    // The part of self that lies to the right of other.
+    pub fn right_of(&self, other: &Self) -> Option<Self> {
+        Self::non_empty(max(self.start, other.end), self.end)
+    }
+
+    // The parts below x and from x on.
+    pub fn split_at(&self, x: i64) -> (Option<Self>, Option<Self>) {
+        (Self::non_empty(self.start, min(self.end, x)),
+         Self::non_empty(max(self.start, x), self.end))
+    }
+
+    pub fn shift(&self, d: i64) -> Self {
+        Interval{ start: self.start + d, end: self.end + d }
+    }
+}
+
+// A set of integers, kept as a sorted list of non-empty intervals that
+// neither overlap nor touch.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet{ intervals: Vec::new() }
+    }
+
+    pub fn from_intervals(intervals: impl IntoIterator<Item = Interval>) -> Self {
+        let mut v = intervals.into_iter().filter(|i| !i.is_empty()).collect::<Vec<_>>();
+        v.sort();
+        let mut merged: Vec<Interval> = Vec::with_capacity(v.len());
+        for i in v {
+            match merged.last_mut() {
+                Some(last) if i.start <= last.end => last.end = max(last.end, i.end),
+                _ => merged.push(i),
+            }
+        }
+        IntervalSet{ intervals: merged }
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // Number of elements.
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(Interval::high)
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        self.intervals.iter().any(|i| i.contains(x))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(self.intervals.iter().chain(other.intervals.iter()).copied())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        let (mut a, mut b) = (self.intervals.iter().peekable(), other.intervals.iter().peekable());
+        while let (Some(&&x), Some(&&y)) = (a.peek(), b.peek()) {
+            if let Some(i) = x.intersection(&y) { result.push(i) }
+            if x.end < y.end { a.next(); } else { b.next(); }
+        }
+        IntervalSet{ intervals: result }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = Vec::new();
+        for &x in &self.intervals {
+            let mut rest = Some(x);
+            for y in other.intervals.iter().take_while(|y| y.start < x.end) {
+                let Some(r) = rest else { break };
+                if let Some(l) = r.left_of(y) { result.push(l) }
+                rest = r.right_of(y);
+            }
+            if let Some(r) = rest { result.push(r) }
+        }
+        IntervalSet{ intervals: result }
+    }
+
+    // The elements below x and those from x on.
+    pub fn split_at(&self, x: i64) -> (Self, Self) {
+        let (low, high): (Vec<_>, Vec<_>) =
+            self.intervals.iter().map(|i| i.split_at(x)).unzip();
+        (IntervalSet{ intervals: low.into_iter().flatten().collect() },
+         IntervalSet{ intervals: high.into_iter().flatten().collect() })
+    }
+
+    pub fn shift(&self, d: i64) -> Self {
+        IntervalSet{ intervals: self.intervals.iter().map(|i| i.shift(d)).collect() }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(i: Interval) -> Self {
+        Self::from_intervals([i])
+    }
+}
+
+// An N-dimensional box: one interval per dimension.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub struct HyperRect<const N: usize>(pub [Interval; N]);
+
+impl<const N: usize> HyperRect<N> {
+    // Number of integer points inside.
+    pub fn volume(&self) -> i64 {
+        self.0.iter().map(Interval::len).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.0.iter().zip(other.0.iter()).all(|(a, b)| a.overlaps(b))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut result = *self;
+        for (a, b) in result.0.iter_mut().zip(other.0.iter()) {
+            *a = a.intersection(b)?;
+        }
+        Some(result)
+    }
+
+    // The same box, with the interval in the given dimension replaced.
+    pub fn with(&self, dim: usize, i: Interval) -> Self {
+        let mut result = *self;
+        result.0[dim] = i;
+        result
+    }
+
+    // The parts below x and from x on in the given dimension.
+    pub fn split_at(&self, dim: usize, x: i64) -> (Option<Self>, Option<Self>) {
+        let (low, high) = self.0[dim].split_at(x);
+        (low.map(|i| self.with(dim, i)), high.map(|i| self.with(dim, i)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::Rng;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        IntervalSet::from_intervals(intervals.iter().map(|&(s, e)| Interval::new(s, e)))
+    }
+
+    fn pairs(s: &IntervalSet) -> Vec<(i64, i64)> {
+        s.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    // Sorted, non-empty, and neither overlapping nor touching.
+    fn normalized(s: &IntervalSet) -> bool {
+        s.iter().all(|i| !i.is_empty()) && s.intervals().windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(pairs(&set(&[(5, 8), (0, 2), (2, 4), (7, 10), (3, 3), (12, 11)])), [(0, 4), (5, 10)]);
+        assert_eq!(pairs(&set(&[(0, 1), (1, 2), (2, 3)])), [(0, 3)]);
+        assert_eq!(pairs(&set(&[(0, 10), (2, 3)])), [(0, 10)]);
+        assert!(set(&[(4, 4)]).is_empty());
+        assert_eq!(set(&[(0, 4), (5, 10)]).len(), 9);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        // Adjacent on both sides, overlapping, inside, and covering.
+        let b = set(&[(5, 10), (12, 13), (14, 20)]);
+        assert_eq!(pairs(&a.union(&b)), [(0, 20)]);
+        assert_eq!(pairs(&a.intersection(&b)), [(12, 13), (14, 15)]);
+        assert_eq!(pairs(&a.difference(&b)), [(0, 5), (10, 12), (13, 14)]);
+        assert_eq!(pairs(&b.difference(&a)), [(5, 10), (15, 20)]);
+        assert!(a.difference(&set(&[(-5, 30)])).is_empty());
+        let (low, high) = a.split_at(12);
+        assert_eq!((pairs(&low), pairs(&high)), (vec![(0, 5), (10, 12)], vec![(12, 15)]));
+        let (low, high) = a.split_at(5);
+        assert_eq!((pairs(&low), pairs(&high)), (vec![(0, 5)], vec![(10, 15)]));
+    }
+
+    // Element by element against random sets of small numbers.
+    #[test]
+    fn random_sets() {
+        let mut rng = Rng::new(1);
+        let random_set = |rng: &mut Rng| {
+            let n = rng.index(5);
+            IntervalSet::from_intervals((0..n).map(|_| {
+                let start = rng.range(0..=25);
+                Interval::new(start, start + rng.range(-1..=6))
+            }))
+        };
+        for _ in 0..500 {
+            let (a, b) = (random_set(&mut rng), random_set(&mut rng));
+            let x = rng.range(-1..=32);
+            let (low, high) = a.split_at(x);
+            for s in [a.union(&b), a.intersection(&b), a.difference(&b), low.clone(), high.clone()] {
+                assert!(normalized(&s), "{:?}", s);
+            }
+            for n in -2..35 {
+                assert_eq!(a.union(&b).contains(n), a.contains(n) || b.contains(n));
+                assert_eq!(a.intersection(&b).contains(n), a.contains(n) && b.contains(n));
+                assert_eq!(a.difference(&b).contains(n), a.contains(n) && !b.contains(n));
+                assert_eq!((low.contains(n), high.contains(n)), (a.contains(n) && n < x, a.contains(n) && n >= x));
+            }
+        }
+    }
+}
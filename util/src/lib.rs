@@ -1,3 +1,18 @@
+pub mod animate;
+pub mod bigint;
+pub mod bench;
+pub mod cli;
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod puzzle;
+pub mod random;
+pub mod render;
+pub mod search;
+
 pub mod iter {
 
     pub trait BoxingIterator : Iterator {
@@ -0,0 +1,225 @@
+// Number theory for the days that combine cycles: gcd/lcm, extended
+// Euclid, the Chinese Remainder Theorem, and modular arithmetic.  Also
+// the checked arithmetic that answers are counted with.
+//
+// Everything that could overflow is checked and returns None (or an
+// Overflow) instead.  Intermediate results are computed with 128 bits.
+
+use std::error::Error;
+use std::fmt;
+
+// The error for answers that do not fit the type they are computed in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl Error for Overflow {}
+
+// Numbers that answers are counted in: u64 with overflow checks, or
+// BigUint, which never overflows.
+pub trait Count: Clone {
+    fn from_u64(n: u64) -> Self;
+    fn plus(&self, other: &Self) -> Result<Self, Overflow>;
+    fn times(&self, other: &Self) -> Result<Self, Overflow>;
+}
+
+impl Count for u64 {
+    fn from_u64(n: u64) -> Self {
+        n
+    }
+
+    fn plus(&self, other: &Self) -> Result<Self, Overflow> {
+        self.checked_add(*other).ok_or(Overflow)
+    }
+
+    fn times(&self, other: &Self) -> Result<Self, Overflow> {
+        self.checked_mul(*other).ok_or(Overflow)
+    }
+}
+
+// A running sum, for adding up answers as the input streams by.  Once it
+// has overflowed it stays that way.
+pub struct Total<N>(Result<N, Overflow>);
+
+impl<N: Count> Default for Total<N> {
+    fn default() -> Self {
+        Total(Ok(N::from_u64(0)))
+    }
+}
+
+impl<N: Count> Total<N> {
+    pub fn add(&mut self, n: Result<N, Overflow>) {
+        self.0 = self.0.as_ref().map_err(|&e| e).and_then(|sum| sum.plus(&n?));
+    }
+
+    pub fn get(self) -> Result<N, Overflow> {
+        self.0
+    }
+}
+
+// The sum of the given numbers.
+pub fn sum<N: Count>(numbers: impl IntoIterator<Item = Result<N, Overflow>>) -> Result<N, Overflow> {
+    numbers.into_iter().try_fold(N::from_u64(0), |sum, n| sum.plus(&n?))
+}
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b > 0 {
+        (a, b) = (b, a % b)
+    }
+    a
+}
+
+// Least common multiple, or None if it does not fit.  The lcm of 0
+// and anything is 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 { return Some(0) }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn ext_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < 0 { (-old_r, -old_s, -old_t) } else { (old_r, old_s, old_t) }
+}
+
+// Extended Euclid: (g, x, y) with g = gcd(a, b) >= 0 and a*x + b*y = g.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = ext_gcd_i128(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+// The x in 0..m with a*x = 1 (mod m), if a and m are coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 { return None }
+    let (g, x, _) = ext_gcd_i128(a as i128, m as i128);
+    (g == 1).then(|| x.rem_euclid(m as i128) as i64)
+}
+
+// a*b mod m, or None for m = 0.
+pub fn mod_mul(a: u64, b: u64, m: u64) -> Option<u64> {
+    if m == 0 { return None }
+    Some((a as u128 * b as u128 % m as u128) as u64)
+}
+
+// base^exp mod m, by repeated squaring, or None for m = 0.
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> Option<u64> {
+    if m == 0 { return None }
+    let (mut base, mut exp) = (base % m, exp);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 { result = mod_mul(result, base, m)? }
+        base = mod_mul(base, base, m)?;
+        exp >>= 1;
+    }
+    Some(result)
+}
+
+// Combines two congruences x = a1 (mod m1) and x = a2 (mod m2) into a
+// single one x = a (mod lcm(m1, m2)).  The moduli need not be coprime.
+fn crt_pair((a1, m1): (i128, i128), (a2, m2): (i128, i128)) -> Option<(i128, i128)> {
+    let (g, p, _) = ext_gcd_i128(m1, m2);
+    let diff = a2 - a1;
+    if diff % g != 0 { return None }
+    let m = m1 / g * m2;
+    if m > i64::MAX as i128 { return None }
+    // p * m1 = g (mod m2), so a1 + m1 * k with k = p * diff / g works.
+    let k = (diff / g % (m2 / g)) * p % (m2 / g);
+    Some(((a1 + m1 * k).rem_euclid(m), m))
+}
+
+// Generalized Chinese Remainder Theorem: given congruences
+// x = residue (mod modulus), returns (x, m) such that the solutions are
+// exactly the numbers congruent to x mod m, with x in 0..m.  None if the
+// congruences contradict each other, a modulus is not positive, or the
+// combined modulus does not fit.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter()
+        .try_fold((0, 1), |acc, (r, m)| {
+            if m <= 0 { return None }
+            crt_pair(acc, ((r as i128).rem_euclid(m as i128), m as i128))
+        })
+        .map(|(x, m)| (x as i64, m as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!((gcd(12, 18), gcd(0, 5), gcd(7, 0), gcd(17, 5)), (6, 5, 7, 1));
+        assert_eq!((lcm(4, 6), lcm(0, 6), lcm(7, 5)), (Some(12), Some(0), Some(35)));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (240, -46), (0, 7), (7, 0), (17, 5)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64, "{} {}", a, b);
+            assert_eq!(a * x + b * y, g, "{} {}", a, b);
+        }
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(mod_inv(3, 0), None);
+        assert_eq!(mod_inv(5, 1), Some(0));
+    }
+
+    #[test]
+    fn modular_powers() {
+        for m in 1..20u64 {
+            for base in 0..20 {
+                let mut expected = 1 % m;
+                for exp in 0..10 {
+                    assert_eq!(mod_pow(base, exp, m), Some(expected), "{}^{} mod {}", base, exp, m);
+                    expected = expected * base % m;
+                }
+            }
+        }
+        assert_eq!(mod_pow(2, 64, u64::MAX), Some(1));
+        assert_eq!(mod_mul(u64::MAX - 1, u64::MAX - 1, u64::MAX), Some(1));
+        assert_eq!((mod_mul(3, 4, 0), mod_pow(3, 4, 0)), (None, None));
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli with a common factor: consistent, and contradictory.
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(3, 6), (3, 6)]), Some((3, 6)));
+        // Residues are taken modulo their modulus.
+        assert_eq!(crt([(-1, 5), (7, 3)]), Some((4, 15)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(1, 1 << 40), (0, (1 << 40) - 1)]), None);
+    }
+
+    #[test]
+    fn totals() {
+        let mut total = Total::<u64>::default();
+        total.add(Ok(u64::MAX - 1));
+        total.add(Ok(1));
+        assert_eq!(total.get(), Ok(u64::MAX));
+        let mut total = Total::<u64>::default();
+        total.add(Ok(u64::MAX));
+        total.add(Ok(1));
+        total.add(Ok(0));
+        assert_eq!(total.get(), Err(Overflow));
+        assert_eq!(sum([Ok(1u64), Err(Overflow), Ok(2)]), Err(Overflow));
+    }
+}
@@ -0,0 +1,216 @@
+// Parse errors shared by all days.
+//
+// Input is parsed line by line.  A Line knows its own line number, so
+// that errors about (a part of) it can say where exactly the problem is.
+// The file name is not known to the parsers themselves; it gets filled
+// in by whoever read the file.
+//
+// Input that is too large to hold in memory can also be read from a
+// stream, one record at a time.  A record is a line, or a part of a line
+// for inputs that separate items by something else, such as commas.  It
+// is handed out as a Line that knows where on its line it starts.
+
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,    // 1-based
+    pub column: usize,  // 1-based, counted in characters
+    pub text: String,   // the offending text
+    pub msg: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, msg: &str) -> Self {
+        ParseError{
+            file: None,
+            line,
+            column,
+            text: text.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    pub fn in_file(self, file: &str) -> Self {
+        ParseError{ file: Some(file.to_string()), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.msg)?;
+        if !self.text.is_empty() {
+            write!(f, ": '{}'", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    pub number: usize,  // 1-based
+    pub offset: usize,  // characters before the text on its line
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    // Error about the given part of the line.  The part is expected to
+    // be a slice of the line's text, which determines the column.  Other
+    // strings are attributed to the beginning of the line.
+    pub fn error(&self, part: &str, msg: &str) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+        let column = match self.text.get(..offset) {
+            Some(before) if offset + part.len() <= self.text.len() =>
+                self.offset + before.chars().count() + 1,
+            _ => 1,
+        };
+        ParseError::new(self.number, column, part, msg)
+    }
+
+    // Error about the character at the given (0-based) character index.
+    pub fn error_at(&self, index: usize, c: char, msg: &str) -> ParseError {
+        ParseError::new(self.number, self.offset + index + 1, &c.to_string(), msg)
+    }
+
+    // Error about the line as a whole.
+    pub fn fail(&self, msg: &str) -> ParseError {
+        ParseError::new(self.number, self.offset + 1, self.text, msg)
+    }
+
+    // Parses the given part of the line.  The description says what
+    // was expected there.
+    pub fn parse<T: FromStr>(&self, part: &str, what: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, &format!("bad {}", what)))
+    }
+}
+
+// The lines of the input, numbered.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line{ number: i + 1, offset: 0, text })
+}
+
+// Error about the end of the input, for when something is missing.
+pub fn eof_error(input: &str, msg: &str) -> ParseError {
+    ParseError::new(input.lines().count() + 1, 1, "", msg)
+}
+
+// Reads the input one record at a time, where records end at the given
+// separator or at the end of a line, and hands each of them to the given
+// function.  Only the current record is held in memory.  A line break at
+// the very end of the input does not start another record, but any other
+// separator does, as with str::split.
+pub fn read_records(input: &mut dyn BufRead, separator: u8,
+                    mut each: impl FnMut(Line) -> Result<(), ParseError>)
+                    -> Result<(), Box<dyn Error>> {
+    let mut buf = Vec::new();
+    let (mut number, mut offset) = (1, 0);
+    let mut pending = false;
+    loop {
+        buf.clear();
+        if input.read_until(separator, &mut buf)? == 0 {
+            if pending {
+                each(Line{ number, offset, text: "" })?;
+            }
+            return Ok(())
+        }
+        let separated = buf.last() == Some(&separator);
+        pending = separated && separator != b'\n';
+        if separated {
+            buf.pop();
+        }
+        let mut pieces = buf.split(|&b| b == b'\n').peekable();
+        while let Some(piece) = pieces.next() {
+            let last = pieces.peek().is_none();
+            if last && !separated && piece.is_empty() {
+                break
+            }
+            let line_ends = !last || separator == b'\n';
+            let piece = match piece {
+                [rest @ .., b'\r'] if line_ends => rest,
+                _ => piece,
+            };
+            let text = std::str::from_utf8(piece)
+                .map_err(|_| ParseError::new(number, offset + 1, "", "invalid UTF-8"))?;
+            each(Line{ number, offset, text })?;
+            if line_ends {
+                (number, offset) = (number + 1, 0);
+            } else {
+                offset += text.chars().count() + 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The records as (line, column, text).
+    fn records(input: &str, separator: u8) -> Result<Vec<(usize, usize, String)>, String> {
+        let mut records = Vec::new();
+        read_records(&mut input.as_bytes(), separator, |line| {
+            records.push((line.number, line.offset + 1, line.text.to_string()));
+            Ok(())
+        }).map_err(|e| e.to_string())?;
+        Ok(records)
+    }
+
+    fn texts(input: &str, separator: u8) -> Vec<String> {
+        records(input, separator).unwrap().into_iter().map(|(_, _, text)| text).collect()
+    }
+
+    #[test]
+    fn separators() {
+        assert_eq!(records("ab,c\nd,ef\n", b',').unwrap(),
+                   [(1, 1, "ab".into()), (1, 4, "c".into()), (2, 1, "d".into()), (2, 3, "ef".into())]);
+        assert_eq!(texts("a\n\nb\n", b'\n'), ["a", "", "b"]);
+        assert_eq!(texts("", b'\n'), [""; 0]);
+        assert_eq!(texts("", b','), [""; 0]);
+    }
+
+    #[test]
+    fn line_ends() {
+        assert_eq!(texts("ab\r\ncd\r\n", b'\n'), ["ab", "cd"]);
+        assert_eq!(texts("a,b\r\nc\r\n", b','), ["a", "b", "c"]);
+        assert_eq!(texts("ab\ncd", b'\n'), ["ab", "cd"]);
+        assert_eq!(texts("a,b", b','), ["a", "b"]);
+        assert_eq!(texts("a,b\nc", b','), ["a", "b", "c"]);
+    }
+
+    // A separator always starts another record, like with str::split,
+    // while a line break at the end does not.
+    #[test]
+    fn empty_records() {
+        assert_eq!(texts("a,b,", b','), ["a", "b", ""]);
+        assert_eq!(texts("a,b,\n", b','), ["a", "b", ""]);
+        assert_eq!(texts("a,,b\n", b','), ["a", "", "b"]);
+        assert_eq!(texts("a\n\n", b'\n'), ["a", ""]);
+    }
+
+    #[test]
+    fn error_positions() {
+        let result = read_records(&mut "ab,cé,x\nyz,!\n".as_bytes(), b',', |line| {
+            match line.text.find(|c: char| !c.is_ascii_alphabetic()) {
+                Some(i) => Err(line.error(&line.text[i..], "bad letter")),
+                None => Ok(()),
+            }
+        });
+        assert_eq!(result.unwrap_err().to_string(), "1:5: bad letter: 'é'");
+        let result = read_records(&mut "ab\nx,!\n".as_bytes(), b',', |line| {
+            if line.text == "!" { Err(line.fail("bang")) } else { Ok(()) }
+        });
+        assert_eq!(result.unwrap_err().to_string(), "2:3: bang: '!'");
+        let result = read_records(&mut &b"ok,\nb\xffd\n"[..], b',', |_| Ok(()));
+        assert_eq!(result.unwrap_err().to_string(), "2:1: invalid UTF-8");
+    }
+}
@@ -0,0 +1,296 @@
+// The common shape of all days: the input is parsed once into a
+// day-specific model, from which each of the two parts computes its
+// answer.  Day-specific settings (command-line options) live in the
+// value implementing the trait.
+//
+// Days whose answers are folds over the records (usually lines) of the
+// input can also be solved as the input streams by, in bounded memory.
+// Such a day says how to read one record and how to add it to the running
+// state of each part; the state then gives the answer at the end.
+
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+use crate::bigint::BigUint;
+use crate::cli::{self, Args, Format, Part, RunResult, UsageError};
+use crate::math::Overflow;
+use crate::parse::{self, Line, ParseError};
+
+pub trait Puzzle {
+    type Model;
+    type Answer1: ToAnswer;
+    type Answer2: ToAnswer;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError>;
+    fn part1(&self, model: &Self::Model) -> Self::Answer1;
+    fn part2(&self, model: &Self::Model) -> Self::Answer2;
+}
+
+pub trait Streaming: Puzzle {
+    type Record;
+    type State1: Default;
+    type State2: Default;
+
+    // What ends a record, other than the end of a line.
+    const SEPARATOR: u8 = b'\n';
+
+    fn record(&self, line: Line) -> Result<Self::Record, ParseError>;
+    fn add1(&self, state: &mut Self::State1, record: &Self::Record);
+    fn add2(&self, state: &mut Self::State2, record: &Self::Record);
+
+    // Called once all records are in, for inputs that can only be
+    // checked as a whole.  The states are those of the wanted parts,
+    // and default ones for the others.
+    fn finish(&self, _state1: &mut Self::State1, _state2: &mut Self::State2) -> Result<(), ParseError> {
+        Ok(())
+    }
+
+    fn answer1(&self, state: Self::State1) -> Self::Answer1;
+    fn answer2(&self, state: Self::State2) -> Self::Answer2;
+}
+
+// An answer as reported: a number, or some text for the rare answers
+// that are not (or when there is no answer at all).  Numbers too large
+// for an i128 are big, all others are not.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Int(i128),
+    Big(BigUint),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Getting the answer fails when computing it overflowed.
+pub trait ToAnswer {
+    fn to_answer(&self) -> Result<Answer, Overflow>;
+}
+
+macro_rules! int_answer {
+    ($($t:ty),*) => {
+        $(impl ToAnswer for $t {
+            fn to_answer(&self) -> Result<Answer, Overflow> {
+                Ok(Answer::Int(*self as i128))
+            }
+        })*
+    }
+}
+
+int_answer!(i32, i64, u32, u64, usize);
+
+// Whole numbers count as integers.
+impl ToAnswer for f64 {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        if self.fract() == 0.0 && self.abs() < 1e30 {
+            Ok(Answer::Int(*self as i128))
+        } else {
+            Ok(Answer::Text(self.to_string()))
+        }
+    }
+}
+
+impl ToAnswer for BigUint {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        Ok(self.to_i128().map_or_else(|| Answer::Big(self.clone()), Answer::Int))
+    }
+}
+
+impl ToAnswer for String {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        Ok(Answer::Text(self.clone()))
+    }
+}
+
+impl ToAnswer for &str {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        Ok(Answer::Text(self.to_string()))
+    }
+}
+
+// An answer, or the reason why there is none.
+impl<T: ToAnswer> ToAnswer for Result<T, &str> {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        match self {
+            Ok(x) => x.to_answer(),
+            Err(reason) => Ok(Answer::Text(reason.to_string())),
+        }
+    }
+}
+
+impl<T: ToAnswer> ToAnswer for Result<T, Overflow> {
+    fn to_answer(&self) -> Result<Answer, Overflow> {
+        self.as_ref().map_err(|&e| e)?.to_answer()
+    }
+}
+
+// The answer to one part, and how long it took to compute (not counting
+// parsing).
+pub struct Solution {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+impl Solution {
+    fn timed<A: ToAnswer>(part: Part, compute: impl FnOnce() -> A) -> Result<Self, Box<dyn Error>> {
+        let start = Instant::now();
+        let answer = compute().to_answer().map_err(|e| format!("part {}: {}", part, e))?;
+        Ok(Solution{ part, answer, elapsed: start.elapsed() })
+    }
+
+    // A JSON record for the given day and input file.
+    pub fn to_json(&self, day: usize, file: &str) -> String {
+        let answer = match &self.answer {
+            Answer::Int(n) => n.to_string(),
+            Answer::Big(n) => n.to_string(),
+            Answer::Text(s) => json_string(s),
+        };
+        format!("{{\"day\": {}, \"file\": {}, \"part\": {}, \"answer\": {}, \"elapsed_ms\": {:.3}}}",
+                day, json_string(file), self.part, answer, self.elapsed.as_secs_f64() * 1000.0)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+pub type Answers = Vec<Solution>;
+pub type SolveResult = Result<Answers, Box<dyn Error>>;
+
+// Solves the requested part(s) for the given input.
+pub fn solve<P: Puzzle>(puzzle: &P, args: &Args, input: &str) -> SolveResult {
+    let model = puzzle.parse(input)?;
+    let mut answers = Vec::new();
+    if args.wants(Part::One) {
+        answers.push(Solution::timed(Part::One, || puzzle.part1(&model))?);
+    }
+    if args.wants(Part::Two) {
+        answers.push(Solution::timed(Part::Two, || puzzle.part2(&model))?);
+    }
+    Ok(answers)
+}
+
+pub type StreamFn = fn(&Args, &mut dyn BufRead) -> SolveResult;
+
+// The final states of the parts after reading the whole input.  Parts
+// that are not wanted stay in their initial state.
+fn fold<P: Streaming>(puzzle: &P, input: &mut dyn BufRead, one: bool, two: bool)
+                      -> Result<(P::State1, P::State2), Box<dyn Error>> {
+    let mut state1 = P::State1::default();
+    let mut state2 = P::State2::default();
+    parse::read_records(input, P::SEPARATOR, |line| {
+        let record = puzzle.record(line)?;
+        if one {
+            puzzle.add1(&mut state1, &record);
+        }
+        if two {
+            puzzle.add2(&mut state2, &record);
+        }
+        Ok(())
+    })?;
+    puzzle.finish(&mut state1, &mut state2)?;
+    Ok((state1, state2))
+}
+
+// Both answers, for input read as it streams.
+pub fn stream_answers<P: Streaming>(puzzle: &P, input: &mut dyn BufRead)
+                                    -> Result<(P::Answer1, P::Answer2), Box<dyn Error>> {
+    let (state1, state2) = fold(puzzle, input, true, true)?;
+    Ok((puzzle.answer1(state1), puzzle.answer2(state2)))
+}
+
+// Solves the requested part(s) while reading the input.  Reading and
+// solving are interleaved, so the time taken for reading counts toward
+// each part.
+pub fn solve_stream<P: Streaming>(puzzle: &P, args: &Args, input: &mut dyn BufRead) -> SolveResult {
+    let start = Instant::now();
+    let (one, two) = (args.wants(Part::One), args.wants(Part::Two));
+    let (state1, state2) = fold(puzzle, input, one, two)?;
+    let read = start.elapsed();
+    let mut answers = Vec::new();
+    if one {
+        answers.push(Solution::timed(Part::One, || puzzle.answer1(state1))?);
+    }
+    if two {
+        answers.push(Solution::timed(Part::Two, || puzzle.answer2(state2))?);
+    }
+    for s in &mut answers {
+        s.elapsed += read;
+    }
+    Ok(answers)
+}
+
+// Solves the requested part(s) of the given day for one input file,
+// which solve reads and solves given its name, and prints the answers in
+// the requested format, after the file name if there are several files.
+// The name comes first, so that whatever the solver shows along the way
+// goes with it.
+fn run_file(day: usize, solve: &dyn Fn(&str) -> SolveResult, args: &Args,
+            file: &str, with_name: bool) -> RunResult {
+    if with_name && args.format == Format::Text {
+        println!("{}:", file);
+    }
+    let answers = solve(file).map_err(|e| match e.downcast::<ParseError>() {
+        Ok(e) => Box::new(e.in_file(file)),
+        Err(e) => e,
+    })?;
+    for s in answers {
+        match args.format {
+            Format::Text => println!("Part {}: {}", s.part, s.answer),
+            Format::Json => println!("{}", s.to_json(day, file)),
+        }
+    }
+    Ok(())
+}
+
+// Runs the given day on each input file named in args.  A file that
+// cannot be read or solved is reported, and the others still get their
+// turn.
+pub fn run(day: usize, solve: fn(&Args, &str) -> SolveResult, args: &Args) -> RunResult {
+    run_files(day, &|file| solve(args, &cli::read_input(file)?), args)
+}
+
+// Like run, but reads each input file as it streams.
+pub fn run_stream(day: usize, stream: StreamFn, args: &Args) -> RunResult {
+    run_files(day, &|file| stream(args, &mut *cli::open_input(file)?), args)
+}
+
+fn run_files(day: usize, solve: &dyn Fn(&str) -> SolveResult, args: &Args) -> RunResult {
+    let files = args.files()?;
+    if let [file] = files {
+        return run_file(day, solve, args, file, false)
+    }
+    let mut failed = 0;
+    for file in files {
+        if let Err(e) = run_file(day, solve, args, file, true) {
+            if e.is::<UsageError>() { return Err(e) }
+            eprintln!("{}", e);
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} of {} inputs failed", failed, files.len()).into())
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// Randomness for generating puzzle inputs.
+//
+// Generated inputs must be reproducible from their seed, so this is a
+// small self-contained generator (SplitMix64) rather than whatever an
+// external crate happens to do in its current version.  It is not meant
+// for anything but tests.
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use crate::direction::Direction;
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng{ state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in 0..n, which must not be empty.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn index(&mut self, n: usize) -> usize {
+        self.below(n as u64) as usize
+    }
+
+    pub fn range(&mut self, r: RangeInclusive<i64>) -> i64 {
+        let (low, high) = r.into_inner();
+        assert!(low <= high, "empty range");
+        let offset = match high.abs_diff(low).checked_add(1) {
+            Some(n) => self.below(n),
+            None => self.next_u64(),  // the full range
+        };
+        low.wrapping_add(offset as i64)
+    }
+
+    // True with probability p.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+
+    // A word of the given length over the given alphabet.
+    pub fn word(&mut self, len: usize, alphabet: &[u8]) -> String {
+        (0..len).map(|_| self.choose(alphabet) as char).collect()
+    }
+
+    // A simple closed loop on the lattice points 0..=height x 0..=width,
+    // as a starting point and the unit steps that lead around and back
+    // to it.  The loop is the boundary of a random blob of up to the
+    // given number of unit squares, grown so that it has no holes and
+    // never touches itself.
+    pub fn closed_loop(&mut self, height: usize, width: usize,
+                       squares: usize) -> ((usize, usize), Vec<Direction>) {
+        assert!(height > 0 && width > 0, "no room for a loop");
+        let start = (self.index(height), self.index(width));
+        let mut blob = HashSet::from([start]);
+        let mut frontier = vec![start];
+        let mut attempts = 0;
+        while blob.len() < squares && attempts < 20 * squares {
+            attempts += 1;
+            let (r, c) = self.choose(&frontier);
+            let d = self.choose(&Direction::ALL);
+            let (dr, dc) = d.delta();
+            let (nr, nc) = (r as i64 + dr, c as i64 + dc);
+            if nr < 0 || nc < 0 || nr >= height as i64 || nc >= width as i64 { continue }
+            let next = (nr as usize, nc as usize);
+            if blob.contains(&next) || !can_add(&blob, next) { continue }
+            blob.insert(next);
+            frontier.push(next);
+        }
+        trace_boundary(&blob)
+    }
+}
+
+// Would adding the square keep the blob free of pinches (squares that
+// only touch at a corner) and holes?  As the blob is connected and has
+// no holes yet, a new hole appears exactly if the empty squares around
+// the new one fall apart into several runs.
+fn can_add(blob: &HashSet<(usize, usize)>, (r, c): (usize, usize)) -> bool {
+    let filled = |(dr, dc): (i64, i64)| {
+        let (nr, nc) = (r as i64 + dr, c as i64 + dc);
+        nr >= 0 && nc >= 0 && blob.contains(&(nr as usize, nc as usize))
+    };
+    for (dr, dc) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+        if filled((dr, dc)) && !filled((dr, 0)) && !filled((0, dc)) { return false }
+    }
+    const RING: [(i64, i64); 8] =
+        [(-1, -1), (-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1)];
+    let empty_runs = (0..8)
+        .filter(|&i| !filled(RING[i]) && filled(RING[(i + 7) % 8]))
+        .count();
+    empty_runs <= 1
+}
+
+// Walks around the blob clockwise, starting at the top left corner of
+// its first square.
+fn trace_boundary(blob: &HashSet<(usize, usize)>) -> ((usize, usize), Vec<Direction>) {
+    use Direction::*;
+    let filled = |r: i64, c: i64| r >= 0 && c >= 0 && blob.contains(&(r as usize, c as usize));
+    let &start = blob.iter().min().expect("non-empty blob");
+    let start = (start.0 as i64, start.1 as i64);
+    // The edge from lattice point p in direction d has the blob on its
+    // right.  Prefer turning left, then going straight, then right.
+    let has_blob_right = |(r, c): (i64, i64), d: Direction| match d {
+        East => filled(r, c) && !filled(r - 1, c),
+        South => filled(r, c - 1) && !filled(r, c),
+        West => filled(r - 1, c - 1) && !filled(r, c - 1),
+        North => filled(r - 1, c) && !filled(r - 1, c - 1),
+    };
+    let mut steps = Vec::new();
+    let mut p = start;
+    let mut d = East;
+    loop {
+        steps.push(d);
+        p = d.step(p);
+        if p == start { break }
+        d = [d.turn_left(), d, d.turn_right()].into_iter()
+            .find(|&e| has_blob_right(p, e))
+            .expect("boundary continues");
+    }
+    ((start.0 as usize, start.1 as usize), steps)
+}
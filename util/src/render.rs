@@ -0,0 +1,165 @@
+// Pictures of what a day has computed, for when a number is not enough
+// to tell what went wrong: every cell of a grid gets a color, and on top
+// of the cells come lines, such as a path through the grid.
+//
+// Coordinates of lines are (row, column) like positions, but in f64 and
+// measured in cells: a cell covers the square from its position to its
+// position plus one in both coordinates, so its center is half a cell
+// further.  Pictures are written as SVG, or as binary PPM for tools that
+// only do raster images, depending on the file name.
+
+use std::error::Error;
+use std::fs;
+
+use crate::cli::{Args, UsageError};
+use crate::grid::{Grid, Pos};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const GRAY: Color = Color(160, 160, 160);
+    pub const RED: Color = Color(220, 40, 40);
+    pub const GREEN: Color = Color(60, 170, 60);
+    pub const BLUE: Color = Color(40, 90, 220);
+    pub const YELLOW: Color = Color(250, 210, 40);
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+pub type Point = (f64, f64);
+
+struct Polyline {
+    points: Vec<Point>,
+    color: Color,
+    closed: bool,
+}
+
+pub struct Picture {
+    cells: Grid<Color>,
+    lines: Vec<Polyline>,
+}
+
+// Pixels per cell in PPM images, and the width of lines in cells.
+const PPM_SCALE: usize = 4;
+const LINE_WIDTH: f64 = 0.25;
+
+impl Picture {
+    pub fn new(height: usize, width: usize, background: Color) -> Self {
+        Picture{ cells: Grid::filled(height, width, background), lines: Vec::new() }
+    }
+
+    // A picture of the grid, with the given colors for its cells.
+    pub fn from<T>(grid: &Grid<T>, color: impl FnMut(&T) -> Color) -> Self {
+        Picture{ cells: grid.map(color), lines: Vec::new() }
+    }
+
+    pub fn highlight(&mut self, cells: impl IntoIterator<Item = Pos>, color: Color) {
+        for p in cells {
+            self.cells[p] = color;
+        }
+    }
+
+    // A line through the given points.
+    pub fn line(&mut self, points: impl IntoIterator<Item = Point>, color: Color) {
+        self.lines.push(Polyline{ points: points.into_iter().collect(), color, closed: false });
+    }
+
+    // A polygon with the given corners.
+    pub fn polygon(&mut self, points: impl IntoIterator<Item = Point>, color: Color) {
+        self.lines.push(Polyline{ points: points.into_iter().collect(), color, closed: true });
+    }
+
+    // A line through the centers of the given cells.
+    pub fn path(&mut self, cells: impl IntoIterator<Item = Pos>, color: Color) {
+        self.line(cells.into_iter().map(|(r, c)| (r as f64 + 0.5, c as f64 + 0.5)), color);
+    }
+
+    // Runs of cells of the same color become one rectangle each, which
+    // keeps the file size reasonable for the larger grids.
+    pub fn to_svg(&self) -> String {
+        let (height, width) = (self.cells.height(), self.cells.width());
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" \
+                               width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+                              width, height, width * PPM_SCALE, height * PPM_SCALE);
+        for (r, row) in self.cells.rows().enumerate() {
+            let mut c = 0;
+            while c < width {
+                let run = row[c..].iter().take_while(|&&color| color == row[c]).count();
+                svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                                      c, r, run, row[c].hex()));
+                c += run;
+            }
+        }
+        for line in &self.lines {
+            let points = line.points.iter()
+                .map(|(r, c)| format!("{},{}", c, r))
+                .collect::<Vec<_>>()
+                .join(" ");
+            svg.push_str(&format!("<{} points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" \
+                                   stroke-linejoin=\"round\" shape-rendering=\"auto\"/>\n",
+                                  if line.closed { "polygon" } else { "polyline" },
+                                  points, line.color.hex(), LINE_WIDTH));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // Lines get drawn by coloring the pixels around points taken along
+    // them at less than a pixel apart.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (height, width) = (self.cells.height() * PPM_SCALE, self.cells.width() * PPM_SCALE);
+        let mut pixels = Grid::new(height, width, (0..height * width)
+            .map(|i| self.cells[(i / width / PPM_SCALE, i % width / PPM_SCALE)])
+            .collect());
+        let scale = PPM_SCALE as f64;
+        let radius = (LINE_WIDTH * scale / 2.0).max(0.5);
+        for line in &self.lines {
+            let mut points = line.points.clone();
+            if line.closed {
+                points.extend(line.points.first());
+            }
+            for pair in points.windows(2) {
+                let ((r0, c0), (r1, c1)) = (pair[0], pair[1]);
+                let steps = (((r1 - r0).abs().max((c1 - c0).abs())) * scale * 2.0).ceil().max(1.0) as usize;
+                for k in 0..=steps {
+                    let t = k as f64 / steps as f64;
+                    let (r, c) = ((r0 + t * (r1 - r0)) * scale, (c0 + t * (c1 - c0)) * scale);
+                    let rows = (r - radius).max(0.0) as usize..((r + radius).ceil() as usize).min(height);
+                    for i in rows {
+                        let columns = (c - radius).max(0.0) as usize..((c + radius).ceil() as usize).min(width);
+                        for j in columns {
+                            pixels[(i, j)] = line.color;
+                        }
+                    }
+                }
+            }
+        }
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        for (_, &Color(r, g, b)) in pixels.iter() {
+            ppm.extend([r, g, b]);
+        }
+        ppm
+    }
+
+    // Writes the picture as PPM if the file name ends in ".ppm", as SVG
+    // otherwise.
+    pub fn write(&self, file: &str) -> Result<(), Box<dyn Error>> {
+        let contents = if file.ends_with(".ppm") { self.to_ppm() } else { self.to_svg().into_bytes() };
+        fs::write(file, contents).map_err(|e| format!("{}: {}", file, e).into())
+    }
+}
+
+// The file named by --render, if any.  With several input files each
+// would overwrite the picture of the one before, so that is refused.
+pub fn target(args: &Args) -> Result<Option<String>, UsageError> {
+    let file = args.value::<String>("render")?;
+    if file.is_some() && args.files.len() > 1 {
+        return Err(UsageError(String::from("--render takes a single input file")))
+    }
+    Ok(file)
+}
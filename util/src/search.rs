@@ -0,0 +1,189 @@
+// Graph search over implicitly given graphs.
+//
+// A graph is described by a start node and a successor function.  For
+// the weighted searches the successors come with the cost of the edge
+// leading to them.  All searches stop at the first node satisfying the
+// goal predicate and return the path to it, start and goal included.
+// Costs start at C::default(), which is zero for the numeric types.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+const NO_PARENT: usize = usize::MAX;
+
+// Nodes seen so far, each with the index of the node it was reached from.
+struct Visited<N> {
+    nodes: Vec<(N, usize)>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Eq + Hash + Clone> Visited<N> {
+    fn new(start: N) -> Self {
+        Visited{ nodes: vec![(start.clone(), NO_PARENT)], index: HashMap::from([(start, 0)]) }
+    }
+
+    fn path_to(&self, mut i: usize) -> Vec<N> {
+        let mut path = Vec::new();
+        while i != NO_PARENT {
+            let (node, parent) = &self.nodes[i];
+            path.push(node.clone());
+            i = *parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// Breadth-first search: a path with the fewest steps.
+pub fn bfs<N, I>(start: N,
+                 mut successors: impl FnMut(&N) -> I,
+                 mut is_goal: impl FnMut(&N) -> bool) -> Option<Vec<N>>
+where N: Eq + Hash + Clone, I: IntoIterator<Item = N> {
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        let node = &visited.nodes[i].0;
+        if is_goal(node) {
+            return Some(visited.path_to(i));
+        }
+        for next in successors(node) {
+            if let Entry::Vacant(e) = visited.index.entry(next.clone()) {
+                e.insert(visited.nodes.len());
+                queue.push_back(visited.nodes.len());
+                visited.nodes.push((next, i));
+            }
+        }
+    }
+    None
+}
+
+// Dijkstra's algorithm: a path of least total cost, together with that
+// cost.  Edge costs must not be negative.
+pub fn dijkstra<N, C, I>(start: N,
+                         successors: impl FnMut(&N) -> I,
+                         is_goal: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where N: Eq + Hash + Clone, C: Copy + Ord + Default + Add<Output = C>,
+      I: IntoIterator<Item = (N, C)> {
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+// A* search: like Dijkstra, but guided by a heuristic that estimates
+// the remaining cost to a goal.  The result is optimal as long as the
+// heuristic never overestimates.
+pub fn astar<N, C, I>(start: N,
+                      mut successors: impl FnMut(&N) -> I,
+                      mut heuristic: impl FnMut(&N) -> C,
+                      mut is_goal: impl FnMut(&N) -> bool) -> Option<(Vec<N>, C)>
+where N: Eq + Hash + Clone, C: Copy + Ord + Default + Add<Output = C>,
+      I: IntoIterator<Item = (N, C)> {
+    let zero = C::default();
+    let mut best = vec![zero];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start);
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > best[i] { continue }; // superseded by a cheaper path
+        let node = &visited.nodes[i].0;
+        if is_goal(node) {
+            return Some((visited.path_to(i), cost));
+        }
+        for (next, step_cost) in successors(node) {
+            let new_cost = cost + step_cost;
+            let j = match visited.index.entry(next.clone()) {
+                Entry::Occupied(e) => {
+                    let j = *e.get();
+                    if best[j] <= new_cost { continue };
+                    best[j] = new_cost;
+                    visited.nodes[j].1 = i;
+                    j
+                },
+                Entry::Vacant(e) => {
+                    let j = visited.nodes.len();
+                    e.insert(j);
+                    best.push(new_cost);
+                    visited.nodes.push((next.clone(), i));
+                    j
+                },
+            };
+            heap.push(Reverse((new_cost + heuristic(&next), new_cost, j)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: [&str; 5] = [
+        "S..#....",
+        ".#.#.##.",
+        ".#...#..",
+        ".####.#.",
+        "......#G",
+    ];
+
+    type Node = (usize, usize);
+
+    fn find(maze: &[&str], c: u8) -> Node {
+        maze.iter().enumerate()
+            .find_map(|(r, row)| row.bytes().position(|b| b == c).map(|col| (r, col)))
+            .unwrap()
+    }
+
+    fn open(maze: &[&str], (r, c): Node) -> Vec<Node> {
+        let mut next = Vec::new();
+        if r > 0 { next.push((r - 1, c)) }
+        if c > 0 { next.push((r, c - 1)) }
+        next.push((r + 1, c));
+        next.push((r, c + 1));
+        next.into_iter()
+            .filter(|&(r, c)| maze.get(r).and_then(|row| row.as_bytes().get(c)).is_some_and(|&b| b != b'#'))
+            .collect()
+    }
+
+    // Steps onto a cell cost its row number plus one.
+    fn weighted(maze: &[&str], node: Node) -> Vec<(Node, u32)> {
+        open(maze, node).into_iter().map(|n| (n, n.0 as u32 + 1)).collect()
+    }
+
+    fn is_path(maze: &[&str], path: &[Node]) -> bool {
+        path.windows(2).all(|w| open(maze, w[0]).contains(&w[1]))
+    }
+
+    #[test]
+    fn bfs_shortest_path() {
+        let (start, goal) = (find(&MAZE, b'S'), find(&MAZE, b'G'));
+        let path = bfs(start, |&n| open(&MAZE, n), |&n| n == goal).unwrap();
+        assert_eq!(path.len() - 1, 15);
+        assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+        assert!(is_path(&MAZE, &path));
+        assert_eq!(bfs(start, |&n| open(&MAZE, n), |&n| n == start), Some(vec![start]));
+        assert_eq!(bfs(start, |&n| open(&MAZE, n), |&n| n == (4, 6)), None);
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        let (start, goal) = (find(&MAZE, b'S'), find(&MAZE, b'G'));
+        let (path, cost) = dijkstra(start, |&n| weighted(&MAZE, n), |&n| n == goal).unwrap();
+        assert!(is_path(&MAZE, &path));
+        assert_eq!(cost, path[1..].iter().map(|n| n.0 as u32 + 1).sum());
+        // Each step costs at least one, so the distance never overestimates.
+        let manhattan = |&(r, c): &Node| (r.abs_diff(goal.0) + c.abs_diff(goal.1)) as u32;
+        let (path, astar_cost) = astar(start, |&n| weighted(&MAZE, n), manhattan, |&n| n == goal).unwrap();
+        assert!(is_path(&MAZE, &path));
+        assert_eq!(astar_cost, cost);
+        assert_eq!(dijkstra(start, |&n| weighted(&MAZE, n), |&n| n == (4, 6)), None);
+    }
+
+    // With unit costs, Dijkstra finds as short a path as BFS.
+    #[test]
+    fn unit_costs() {
+        let (start, goal) = (find(&MAZE, b'S'), find(&MAZE, b'G'));
+        let unit = |&n: &Node| open(&MAZE, n).into_iter().map(|n| (n, 1)).collect::<Vec<_>>();
+        let (_, cost) = dijkstra(start, unit, |&n| n == goal).unwrap();
+        assert_eq!(cost, 15);
+    }
+}